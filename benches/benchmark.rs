@@ -49,6 +49,80 @@ fn criterion_benchmark(c: &mut Criterion) {
         });
     });
 
+    c.bench_function("fast_hilbert_morton", |b| {
+        b.iter(|| {
+            for x in 0..n {
+                for y in 0..n {
+                    black_box(fast_hilbert::xy2morton(
+                        black_box(x as u32),
+                        black_box(y as u32),
+                    ));
+                }
+            }
+        });
+    });
+
+    let xs: Vec<u32> = (0..n * n).map(|i| (i / n) as u32).collect();
+    let ys: Vec<u32> = (0..n * n).map(|i| (i % n) as u32).collect();
+    let mut hs = vec![0u64; n * n];
+
+    c.bench_function("fast_hilbert_slice", |b| {
+        b.iter(|| {
+            fast_hilbert::xy2h_slice_soa(
+                black_box(&xs),
+                black_box(&ys),
+                black_box(bits as u8),
+                &mut hs,
+            );
+            black_box(&hs);
+        });
+    });
+
+    #[cfg(feature = "simd")]
+    c.bench_function("fast_hilbert_bulk", |b| {
+        b.iter(|| {
+            fast_hilbert::xy2h_bulk(
+                black_box(&xs),
+                black_box(&ys),
+                black_box(bits as u8),
+                &mut hs,
+            );
+            black_box(&hs);
+        });
+    });
+
+    let neighbor_order: u8 = 16;
+    let keys: Vec<u64> = (0..(n * n) as u64)
+        .map(|i| i.wrapping_mul(0x9E37_79B9) & 0xFFFF_FFFF)
+        .collect();
+
+    c.bench_function("fast_hilbert_neighbors", |b| {
+        b.iter(|| {
+            for &h in &keys {
+                black_box(fast_hilbert::neighbor_keys::<u32>(
+                    black_box(h),
+                    black_box(neighbor_order),
+                ));
+            }
+        });
+    });
+
+    c.bench_function("fast_hilbert_neighbors_naive", |b| {
+        b.iter(|| {
+            for &h in &keys {
+                let order = black_box(neighbor_order);
+                let (x, y) = fast_hilbert::h2xy::<u32>(black_box(h), order);
+                let max = (1u32 << order) - 1;
+                black_box([
+                    (y < max).then(|| fast_hilbert::xy2h(x, y + 1, order)),
+                    (y > 0).then(|| fast_hilbert::xy2h(x, y - 1, order)),
+                    (x > 0).then(|| fast_hilbert::xy2h(x - 1, y, order)),
+                    (x < max).then(|| fast_hilbert::xy2h(x + 1, y, order)),
+                ]);
+            }
+        });
+    });
+
     let xy_low: (u32, u32) = (1, 2);
     let xy_high: (u32, u32) = (u32::MAX - 1, u32::MAX - 2);
     let order: u8 = 32;
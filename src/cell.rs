@@ -0,0 +1,243 @@
+use crate::{assert_order, children, h2xy, low_bits, max_order, parent, Unsigned, UnsignedBase};
+use core::ops::RangeInclusive;
+
+/// Quadtree cell of the hilbert curve, which is identified by its `level` and its hilbert `index` at that level.
+///
+/// Because the index of a cell is the prefix of the indices of all cells and points within it, the whole
+/// hierarchy can be walked without decoding the keys. The cell is a small `Copy` value which can be used as
+/// key of a map. Ordering is by level first and by curve order second.
+///
+/// # Examples
+///```
+/// use fast_hilbert::{xy2h, HilbertCell};
+///
+/// let h = xy2h(5u32, 2, 8);
+/// let cell = HilbertCell::<u32>::containing(h, 8, 3);
+/// assert_eq!(cell.level(), 3);
+/// assert!(cell.contains(h, 8));
+/// assert!(cell.parent().unwrap().is_ancestor_of(cell));
+/// assert_eq!(cell.bounding_box(8), (0, 0, 31, 31));
+///```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HilbertCell<T: Unsigned> {
+    level: u8,
+    index: T::Key,
+}
+
+impl<T: Unsigned> HilbertCell<T> {
+    /// The single cell at level zero which covers the whole curve
+    #[must_use]
+    pub fn root() -> Self {
+        Self {
+            level: 0,
+            index: T::Key::ZERO,
+        }
+    }
+
+    /// Create the cell with the hilbert `index` at `level`.
+    ///
+    /// # Panics
+    /// If `level` exceeds the bits of `T` or `index` exceeds the indices of the level.
+    pub fn new(index: T::Key, level: u8) -> Self {
+        assert_order::<T>(level);
+        assert!(
+            index <= low_bits(u32::from(level) << 1),
+            "index exceeds the level"
+        );
+        Self { level, index }
+    }
+
+    /// The cell at `level` which contains the hilbert index `h` of a curve with `order`.
+    ///
+    /// # Panics
+    /// If `level` exceeds `order` or `order` exceeds the bits of `T`.
+    pub fn containing(h: T::Key, order: u8, level: u8) -> Self {
+        check_order::<T>(level, order);
+        Self::new(shr(h, u32::from(order - level) << 1), level)
+    }
+
+    /// Level of the cell, which is the order of the curve its index belongs to
+    #[must_use]
+    pub fn level(self) -> u8 {
+        self.level
+    }
+
+    /// Hilbert index of the cell at its level
+    #[must_use]
+    pub fn index(self) -> T::Key {
+        self.index
+    }
+
+    /// The cell at the level above, or `None` for the root cell
+    #[must_use]
+    pub fn parent(self) -> Option<Self> {
+        parent(self.index, self.level).map(|index| Self {
+            level: self.level - 1,
+            index,
+        })
+    }
+
+    /// The four cells at the level below in curve order, or `None` if the cell is already at the finest level
+    #[must_use]
+    pub fn children(self) -> Option<[Self; 4]> {
+        if self.level == max_order::<T>() {
+            return None;
+        }
+        children(self.index, self.level).map(|indices| {
+            indices.map(|index| Self {
+                level: self.level + 1,
+                index,
+            })
+        })
+    }
+
+    /// Whether the hilbert index `h` of a curve with `order` lies within the cell.
+    ///
+    /// # Panics
+    /// If the level of the cell exceeds `order` or `order` exceeds the bits of `T`.
+    pub fn contains(self, h: T::Key, order: u8) -> bool {
+        self.range_at_order(order).contains(&h)
+    }
+
+    /// Whether `other` is a cell at a finer level within this cell. A cell is not its own ancestor.
+    #[must_use]
+    pub fn is_ancestor_of(self, other: Self) -> bool {
+        other.level > self.level
+            && other.index >> (usize::from(other.level - self.level) << 1) == self.index
+    }
+
+    /// Range of the hilbert indices of a curve with `order` which lie within the cell.
+    ///
+    /// # Panics
+    /// If the level of the cell exceeds `order` or `order` exceeds the bits of `T`.
+    pub fn range_at_order(self, order: u8) -> RangeInclusive<T::Key> {
+        check_order::<T>(self.level, order);
+        let shift = u32::from(order - self.level) << 1;
+        let first = shl(self.index, shift);
+        first..=first | low_bits(shift)
+    }
+
+    /// Inclusive bounding box `(x_min, y_min, x_max, y_max)` of the cell in the coordinates of a curve with
+    /// `order`.
+    ///
+    /// # Panics
+    /// If the level of the cell exceeds `order` or `order` exceeds the bits of `T`.
+    pub fn bounding_box(self, order: u8) -> (T, T, T, T) {
+        check_order::<T>(self.level, order);
+        let (x, y): (T, T) = h2xy(self.index, self.level);
+        let shift = order - self.level;
+        let shift = u32::from(shift);
+        let (x_min, y_min) = (shl(x, shift), shl(y, shift));
+        let size = low_bits::<T>(shift);
+        (x_min, y_min, x_min | size, y_min | size)
+    }
+}
+
+// The root cell spans the whole type, so shifts by the full width have to result in zero
+fn shl<U: UnsignedBase>(value: U, shift: u32) -> U {
+    if shift as usize >= size_of::<U>() << 3 {
+        U::ZERO
+    } else {
+        value << shift as usize
+    }
+}
+
+fn shr<U: UnsignedBase>(value: U, shift: u32) -> U {
+    if shift as usize >= size_of::<U>() << 3 {
+        U::ZERO
+    } else {
+        value >> shift as usize
+    }
+}
+
+#[track_caller]
+fn check_order<T: Unsigned>(level: u8, order: u8) {
+    assert_order::<T>(order);
+    assert!(level <= order, "level exceeds the order");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xy2h;
+    use std::collections::HashSet;
+
+    #[test]
+    fn cell_hierarchy() {
+        let order = 5;
+        for x in 0..32u8 {
+            for y in 0..32u8 {
+                let h = xy2h(x, y, order);
+                let mut cell = HilbertCell::<u8>::containing(h, order, order);
+                assert_eq!(cell.bounding_box(order), (x, y, x, y));
+                while let Some(parent) = cell.parent() {
+                    assert!(parent.is_ancestor_of(cell));
+                    assert!(!cell.is_ancestor_of(parent));
+                    assert!(parent.children().unwrap().contains(&cell));
+                    assert!(parent.contains(h, order));
+                    assert_eq!(HilbertCell::containing(h, order, parent.level()), parent);
+                    let (x_min, y_min, x_max, y_max) = parent.bounding_box(order);
+                    assert!(x_min <= x && x <= x_max && y_min <= y && y <= y_max);
+                    assert_eq!(x_max - x_min, (1 << (order - parent.level())) - 1);
+                    cell = parent;
+                }
+                assert_eq!(cell, HilbertCell::root());
+                assert!(cell.is_ancestor_of(HilbertCell::containing(h, order, order)));
+            }
+        }
+    }
+
+    #[test]
+    fn cell_children_cover_range() {
+        let order = 6;
+        for level in 0..order {
+            for index in 0..1u16 << (2 * level) {
+                let cell = HilbertCell::<u8>::new(index, level);
+                let range = cell.range_at_order(order);
+                let children = cell.children().unwrap();
+                assert_eq!(*children[0].range_at_order(order).start(), *range.start());
+                assert_eq!(*children[3].range_at_order(order).end(), *range.end());
+                for pair in children.windows(2) {
+                    let end = *pair[0].range_at_order(order).end();
+                    assert_eq!(end + 1, *pair[1].range_at_order(order).start());
+                }
+                // All points of the range are inside of the bounding box
+                let (x_min, y_min, x_max, y_max) = cell.bounding_box(order);
+                for h in range {
+                    let (x, y) = h2xy::<u8>(h, order);
+                    assert!(x_min <= x && x <= x_max && y_min <= y && y <= y_max);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn cell_limits() {
+        let root = HilbertCell::<u32>::root();
+        assert_eq!(root.parent(), None);
+        assert_eq!(root.range_at_order(32), 0..=u64::MAX);
+        assert_eq!(root.bounding_box(32), (0, 0, u32::MAX, u32::MAX));
+        assert_eq!(HilbertCell::<u32>::containing(u64::MAX, 32, 0), root);
+        let finest = HilbertCell::<u32>::new(u64::MAX, 32);
+        assert_eq!(finest.children(), None);
+        assert_eq!(finest.range_at_order(32), u64::MAX..=u64::MAX);
+        assert_eq!(finest.bounding_box(32), (u32::MAX, 0, u32::MAX, 0));
+
+        let cells: HashSet<_> = (0..16)
+            .map(|h| HilbertCell::<u8>::containing(h, 2, 1))
+            .collect();
+        assert_eq!(cells.len(), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "level exceeds the order")]
+    fn cell_finer_than_order() {
+        let _ = HilbertCell::<u32>::new(0, 4).range_at_order(3);
+    }
+
+    #[test]
+    #[should_panic(expected = "index exceeds the level")]
+    fn cell_index_beyond_level() {
+        let _ = HilbertCell::<u32>::new(16, 2);
+    }
+}
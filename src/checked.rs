@@ -1,4 +1,4 @@
-use crate::{h2xy, xy2h, Unsigned, UnsignedBase};
+use crate::{h2xy, max_order, xy2h, Unsigned, UnsignedBase};
 use core::cmp::Ordering;
 
 /// The main error type for this crate
@@ -38,12 +38,6 @@ impl<T: Unsigned> core::fmt::Display for OrderError<T> {
 }
 impl<T: Unsigned> core::error::Error for OrderError<T> {}
 
-/// The maximum allowed order for a given type. This is the number of bits in the type.
-#[inline]
-pub fn max_order<T: Unsigned>() -> u8 {
-    (size_of::<T>() << 3) as u8
-}
-
 /// The maximum allowed `x` or `y` coordinate for a given hilbert order
 ///
 /// returns [`OrderError`] if the order doesn't fit in the current type
@@ -0,0 +1,260 @@
+use crate::iter::Walker;
+use crate::{assert_order, low_bits, xy2h, Unsigned, UnsignedCurve};
+
+/// Mutable position on the hilbert curve which keeps its coordinates and its index in sync.
+///
+/// The cursor remembers the state of every level, so a move only encodes or decodes the levels below the highest
+/// changed bit. Unit steps cost amortized `O(1)` instead of the `O(order)` of [`xy2h`] or
+/// [`h2xy`](crate::h2xy). Moves beyond the border of the curve return `false` and leave the cursor unchanged.
+///
+/// # Examples
+///```
+/// use fast_hilbert::{xy2h, HilbertCursor};
+///
+/// let mut cursor = HilbertCursor::new(3u32, 4, 8);
+/// assert!(cursor.move_right());
+/// assert!(cursor.move_up());
+/// assert_eq!((cursor.x(), cursor.y()), (4, 5));
+/// assert_eq!(cursor.h(), xy2h(4u32, 5, 8));
+///
+/// assert!(cursor.advance(2));
+/// assert_eq!(cursor.h(), xy2h(4u32, 5, 8) + 2);
+/// assert!(!cursor.retreat(u64::MAX));
+///```
+#[derive(Clone, Debug)]
+pub struct HilbertCursor<T: Unsigned> {
+    walker: Walker<T>,
+}
+
+impl<T: UnsignedCurve> HilbertCursor<T> {
+    /// Create a cursor at the cell `(x, y)` of the curve with `order`.
+    ///
+    /// # Panics
+    /// If `order` exceeds the number of bits of `T` or a coordinate is outside of the curve.
+    pub fn new(x: T, y: T, order: u8) -> Self {
+        assert_order::<T>(order);
+        let max: T = low_bits(order.into());
+        assert!(
+            x <= max && y <= max,
+            "coordinate ({x:?}, {y:?}) is outside of the curve with order {order}"
+        );
+        Self {
+            walker: Walker::new(xy2h(x, y, order), order),
+        }
+    }
+
+    /// Create a cursor at the hilbert index `h` of the curve with `order`.
+    ///
+    /// # Panics
+    /// If `order` exceeds the number of bits of `T` or `h` exceeds the indices of `order`.
+    pub fn from_h(h: T::Key, order: u8) -> Self {
+        assert_order::<T>(order);
+        assert!(
+            h <= last_index::<T>(order),
+            "index {h:?} exceeds the indices of order {order}"
+        );
+        Self {
+            walker: Walker::new(h, order),
+        }
+    }
+
+    /// Coordinate in 2D space
+    #[must_use]
+    pub fn x(&self) -> T {
+        self.walker.x
+    }
+
+    /// Coordinate in 2D space
+    #[must_use]
+    pub fn y(&self) -> T {
+        self.walker.y
+    }
+
+    /// Coordinate in 1D hilbert space
+    #[must_use]
+    pub fn h(&self) -> T::Key {
+        self.walker.h
+    }
+
+    /// The hilbert curve order
+    #[must_use]
+    pub fn order(&self) -> u8 {
+        self.walker.order()
+    }
+
+    /// Move to `(x - 1, y)`. Returns `false` at the left border.
+    pub fn move_left(&mut self) -> bool {
+        let (x, y) = (self.x(), self.y());
+        let inside = x > T::ZERO;
+        if inside {
+            self.walker.move_to(x - T::from(1), y);
+        }
+        inside
+    }
+
+    /// Move to `(x + 1, y)`. Returns `false` at the right border.
+    pub fn move_right(&mut self) -> bool {
+        let (x, y) = (self.x(), self.y());
+        let inside = x < self.max_coord();
+        if inside {
+            self.walker.move_to(x + T::from(1), y);
+        }
+        inside
+    }
+
+    /// Move to `(x, y + 1)`. Returns `false` at the upper border.
+    pub fn move_up(&mut self) -> bool {
+        let (x, y) = (self.x(), self.y());
+        let inside = y < self.max_coord();
+        if inside {
+            self.walker.move_to(x, y + T::from(1));
+        }
+        inside
+    }
+
+    /// Move to `(x, y - 1)`. Returns `false` at the lower border.
+    pub fn move_down(&mut self) -> bool {
+        let (x, y) = (self.x(), self.y());
+        let inside = y > T::ZERO;
+        if inside {
+            self.walker.move_to(x, y - T::from(1));
+        }
+        inside
+    }
+
+    /// Move `n` cells forward along the curve. Returns `false` if that is beyond the last cell.
+    pub fn advance(&mut self, n: T::Key) -> bool {
+        let h = self.h();
+        if n > last_index::<T>(self.order()) - h {
+            return false;
+        }
+        self.walker.jump(h + n);
+        true
+    }
+
+    /// Move `n` cells backward along the curve. Returns `false` if that is before the first cell.
+    pub fn retreat(&mut self, n: T::Key) -> bool {
+        let h = self.h();
+        if n > h {
+            return false;
+        }
+        self.walker.jump(h - n);
+        true
+    }
+
+    fn max_coord(&self) -> T {
+        low_bits(self.order().into())
+    }
+}
+
+fn last_index<T: Unsigned>(order: u8) -> T::Key {
+    low_bits(u32::from(order) << 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::h2xy;
+
+    fn check<T: UnsignedCurve>(cursor: &HilbertCursor<T>) {
+        assert_eq!(cursor.h(), xy2h(cursor.x(), cursor.y(), cursor.order()));
+    }
+
+    #[test]
+    fn cursor_moves_match_xy2h() {
+        for order in 0..=8u8 {
+            let max = low_bits::<u8>(order.into());
+            // Walk along every row in both directions and then along every column
+            let mut cursor = HilbertCursor::<u8>::new(0, 0, order);
+            for y in 0..=max {
+                for x in 1..=max {
+                    assert!(cursor.move_right());
+                    assert_eq!((cursor.x(), cursor.y()), (x, y));
+                    check(&cursor);
+                }
+                assert!(!cursor.move_right());
+                for x in (0..max).rev() {
+                    assert!(cursor.move_left());
+                    assert_eq!((cursor.x(), cursor.y()), (x, y));
+                    check(&cursor);
+                }
+                assert!(!cursor.move_left());
+                assert_eq!(cursor.move_up(), y < max);
+            }
+            for x in 0..=max {
+                cursor = HilbertCursor::new(x, max, order);
+                for y in (0..max).rev() {
+                    assert!(cursor.move_down());
+                    assert_eq!((cursor.x(), cursor.y()), (x, y));
+                    check(&cursor);
+                }
+                assert!(!cursor.move_down());
+                for _ in 0..max {
+                    assert!(cursor.move_up());
+                    check(&cursor);
+                }
+                assert!(!cursor.move_up());
+            }
+        }
+    }
+
+    #[test]
+    fn cursor_advance_and_retreat() {
+        for order in 0..=6u8 {
+            let last = low_bits::<u16>(u32::from(order) << 1);
+            for step in [1, 2, 3, 5, 16, 61] {
+                let mut cursor = HilbertCursor::<u8>::from_h(0, order);
+                let mut h = 0;
+                while last - h >= step {
+                    assert!(cursor.advance(step));
+                    h += step;
+                    assert_eq!(cursor.h(), h);
+                    assert_eq!((cursor.x(), cursor.y()), h2xy(h, order));
+                }
+                assert!(!cursor.advance(step));
+                while h >= step {
+                    assert!(cursor.retreat(step));
+                    h -= step;
+                    assert_eq!((cursor.x(), cursor.y()), h2xy(h, order));
+                }
+                assert!(!cursor.retreat(step));
+                assert_eq!(cursor.h(), h);
+                // Coordinates and index stay in sync after mixing both kinds of moves
+                if cursor.move_up() {
+                    assert!(cursor.advance(0));
+                    check(&cursor);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn cursor_full_order() {
+        let mid = 1u32 << 31;
+        let mut cursor = HilbertCursor::new(mid - 1, mid - 1, 32);
+        assert!(cursor.move_right());
+        assert!(cursor.move_up());
+        assert_eq!((cursor.x(), cursor.y()), (mid, mid));
+        check(&cursor);
+        assert!(cursor.move_left());
+        assert!(cursor.move_down());
+        check(&cursor);
+
+        let mut cursor = HilbertCursor::<u32>::from_h(0, 32);
+        assert!(cursor.advance(u64::MAX));
+        assert_eq!((cursor.x(), cursor.y()), (u32::MAX, 0));
+        assert!(!cursor.advance(1));
+        assert!(!cursor.move_right());
+        assert!(cursor.retreat(u64::MAX >> 1));
+        check(&cursor);
+        assert!(cursor.retreat(u64::MAX >> 1));
+        assert_eq!(cursor.h(), 1);
+        check(&cursor);
+    }
+
+    #[test]
+    #[should_panic(expected = "outside of the curve")]
+    fn cursor_outside_of_curve() {
+        let _ = HilbertCursor::new(0u32, 8, 3);
+    }
+}
@@ -0,0 +1,258 @@
+use crate::{
+    assert_order, h2xy, low_bits, xy2h, UnsignedBase, UnsignedCurve, UnsignedOps, LUT_3,
+    LUT_SH2SXY, LUT_SXY2SH,
+};
+
+// Changed key suffixes of up to this many levels are re-encoded with a single lookup of `LUT_3`, longer ones
+// with `xy2h`
+const SUFFIX_LEVELS: u32 = 3;
+
+/// Direction of a single step between two neighbouring cells
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dir {
+    /// Towards larger `y`
+    Up,
+    /// Towards smaller `y`
+    Down,
+    /// Towards smaller `x`
+    Left,
+    /// Towards larger `x`
+    Right,
+}
+
+impl Dir {
+    /// Direction from the quadrant `from` to the neighbouring quadrant `to`, both given as `XY` bits
+    fn between(from: u8, to: u8) -> Self {
+        match (from ^ to, to & (from ^ to)) {
+            (0b10, 0) => Dir::Left,
+            (0b10, _) => Dir::Right,
+            (_, 0) => Dir::Down,
+            _ => Dir::Up,
+        }
+    }
+}
+
+/// Directions from the cell `(x, y)` to its predecessor and its successor on the curve with `order`.
+///
+/// Returns `None` for the predecessor of the first cell and the successor of the last cell of the curve.
+/// Both directions are found in a single pass over the levels of the state machine. Consecutive quadrants of a
+/// node share an edge, so the step between two cells is the step between the quadrants of the lowest level at
+/// which their indices differ.
+///
+/// # Arguments
+/// * `x` - Coordinate in 2D space
+/// * `y` - Coordinate in 2D space
+/// * `order` - The hilbert curve order
+///
+/// # Panics
+/// If `order` exceeds the number of bits of `T` or a coordinate is outside of the curve.
+///
+/// # Examples
+///```
+/// use fast_hilbert::{curve_direction, Dir};
+///
+/// // The curve of order one visits (0, 0), (0, 1), (1, 1) and (1, 0)
+/// assert_eq!(curve_direction(0u32, 0, 1), (None, Some(Dir::Up)));
+/// assert_eq!(curve_direction(1u32, 1, 1), (Some(Dir::Left), Some(Dir::Down)));
+///```
+pub fn curve_direction<T: UnsignedCurve>(x: T, y: T, order: u8) -> (Option<Dir>, Option<Dir>) {
+    assert_order::<T>(order);
+    let max: T = low_bits(order.into());
+    assert!(
+        x <= max && y <= max,
+        "coordinate ({x:?}, {y:?}) is outside of the curve with order {order}"
+    );
+
+    let mut state = 0u8;
+    // The `SSHH` of the lowest levels whose digit can be decremented or incremented
+    let (mut prev, mut next) = (None, None);
+    for level in (0..usize::from(order)).rev() {
+        let xy = ((x >> level).as_u8() & 1) << 1 | (y >> level).as_u8() & 1;
+        let sh = LUT_SXY2SH[usize::from(state << 2 | xy)];
+        let state_digit = state << 2 | sh & 0b11;
+        if sh & 0b11 != 0 {
+            prev = Some(state_digit);
+        }
+        if sh & 0b11 != 0b11 {
+            next = Some(state_digit);
+        }
+        state = sh >> 2;
+    }
+
+    let step = |state_digit: u8, to_digit: u8| {
+        let from = LUT_SH2SXY[usize::from(state_digit)] & 0b11;
+        let to = LUT_SH2SXY[usize::from(state_digit & 0b1100 | to_digit)] & 0b11;
+        Dir::between(from, to)
+    };
+    (
+        prev.map(|sh| step(sh, (sh & 0b11) - 1)),
+        next.map(|sh| step(sh, (sh & 0b11) + 1)),
+    )
+}
+
+/// Hilbert indices of the four grid neighbours of the cell with the index `h` on the curve with `order`.
+///
+/// The neighbours are returned in the order `[up, down, left, right]` of [`Dir`], with `None` for neighbours
+/// beyond the border of the grid. A neighbour only differs in the lowest levels in which its coordinate carries
+/// over, so only this suffix of the key is re-encoded. The state machine at the start of the suffix is derived
+/// from the number of `0` and `3` digits of the unchanged prefix. Long suffixes fall back to [`xy2h`].
+///
+/// # Arguments
+/// * `h` - Coordinate in 1D hilbert space
+/// * `order` - The hilbert curve order
+///
+/// # Panics
+/// If `order` exceeds the number of bits of `T` or `h` exceeds the indices of `order`.
+///
+/// # Examples
+///```
+/// use fast_hilbert::{neighbor_keys, xy2h};
+///
+/// let h = xy2h(0u32, 5, 3);
+/// let [up, down, left, right] = neighbor_keys::<u32>(h, 3);
+/// assert_eq!(up, Some(xy2h(0u32, 6, 3)));
+/// assert_eq!(down, Some(xy2h(0u32, 4, 3)));
+/// assert_eq!(left, None);
+/// assert_eq!(right, Some(xy2h(1u32, 5, 3)));
+///```
+pub fn neighbor_keys<T: UnsignedCurve>(h: T::Key, order: u8) -> [Option<T::Key>; 4] {
+    assert_order::<T>(order);
+    assert!(
+        h <= low_bits(u32::from(order) << 1),
+        "index {h:?} exceeds the indices of order {order}"
+    );
+    let (x, y): (T, T) = h2xy(h, order);
+    let max: T = low_bits(order.into());
+    let one = T::from(1);
+    // The number of changed levels is one more than the number of bits which carry over
+    [
+        (y < max).then(|| (x, y + one, (!y).trailing_zeros() + 1)),
+        (y > T::ZERO).then(|| (x, y - one, y.trailing_zeros() + 1)),
+        (x > T::ZERO).then(|| (x - one, y, x.trailing_zeros() + 1)),
+        (x < max).then(|| (x + one, y, (!x).trailing_zeros() + 1)),
+    ]
+    .map(|neighbor| neighbor.map(|(x, y, levels)| replace_suffix(h, x, y, levels, order)))
+}
+
+/// Replace the lowest `levels` digits of `h` by the digits of the cell `(x, y)`, which shares the remaining prefix
+fn replace_suffix<T: UnsignedCurve>(h: T::Key, x: T, y: T, levels: u32, order: u8) -> T::Key {
+    if levels > SUFFIX_LEVELS || order < SUFFIX_LEVELS as u8 {
+        return xy2h(x, y, order);
+    }
+    let prefix = h >> ((SUFFIX_LEVELS as usize) << 1);
+    // The digits 0 and 3 toggle the low and the high bit of the state, the digits 1 and 2 keep it.
+    // The prefix has no digits above its levels, so the low bits of all digits can be counted.
+    let low_digit_bits = !T::Key::ZERO / T::Key::from(3);
+    let threes = (prefix & (prefix >> 1usize) & low_digit_bits).count_ones();
+    let prefix_levels = u32::from(order) - SUFFIX_LEVELS;
+    let zeros = prefix_levels - ((prefix | (prefix >> 1usize)) & low_digit_bits).count_ones();
+    let state = (threes & 1) << 1 | zeros & 1;
+
+    let xy = (x.as_u8() & 0b111) << 3 | y.as_u8() & 0b111;
+    let hhh = LUT_3[(state as usize) << 6 | usize::from(xy)] & 0b11_1111;
+    prefix << ((SUFFIX_LEVELS as usize) << 1) | T::Key::from(hhh)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn direction(from: (u8, u8), to: (u8, u8)) -> Dir {
+        match (
+            i16::from(to.0) - i16::from(from.0),
+            i16::from(to.1) - i16::from(from.1),
+        ) {
+            (1, 0) => Dir::Right,
+            (-1, 0) => Dir::Left,
+            (0, 1) => Dir::Up,
+            (0, -1) => Dir::Down,
+            step => panic!("cells {from:?} and {to:?} are not neighbours, step {step:?}"),
+        }
+    }
+
+    #[test]
+    fn directions_of_all_cells() {
+        for order in 0..=7u8 {
+            let last = (1u32 << (2 * order)) - 1;
+            for x in 0..=low_bits::<u8>(order.into()) {
+                for y in 0..=low_bits::<u8>(order.into()) {
+                    let h = xy2h(x, y, order);
+                    let prev = (h > 0).then(|| h2xy(h - 1, order));
+                    let next = (u32::from(h) < last).then(|| h2xy(h + 1, order));
+                    let (prev, next) = (
+                        prev.map(|cell| direction((x, y), cell)),
+                        next.map(|cell| direction((x, y), cell)),
+                    );
+                    assert_eq!(
+                        curve_direction(x, y, order),
+                        (prev, next),
+                        "({x}, {y}) order {order}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn directions_full_order() {
+        assert_eq!(curve_direction(0u64, 0, 64), (None, Some(Dir::Right)));
+        // The curve of an even order ends with a step to the right along the lower border
+        assert_eq!(h2xy::<u64>(u128::MAX - 1, 64), (u64::MAX - 1, 0));
+        assert_eq!(curve_direction(u64::MAX, 0, 64), (Some(Dir::Left), None));
+        assert_eq!(curve_direction(0u64, 0, 63), (None, Some(Dir::Up)));
+    }
+
+    #[test]
+    fn neighbors_of_all_cells() {
+        for order in 0..=7u8 {
+            let max = low_bits::<u8>(order.into());
+            for h in 0..=low_bits::<u16>(u32::from(order) << 1) {
+                let (x, y) = h2xy::<u8>(h, order);
+                let expected = [
+                    (y < max).then(|| xy2h(x, y + 1, order)),
+                    (y > 0).then(|| xy2h(x, y - 1, order)),
+                    (x > 0).then(|| xy2h(x - 1, y, order)),
+                    (x < max).then(|| xy2h(x + 1, y, order)),
+                ];
+                assert_eq!(
+                    neighbor_keys::<u8>(h, order),
+                    expected,
+                    "h {h} order {order}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn neighbors_full_order() {
+        let mid = 1u32 << 31;
+        for (x, y) in [
+            (0, 0),
+            (mid - 1, mid),
+            (mid, 3),
+            (u32::MAX, 0),
+            (5, u32::MAX),
+        ] {
+            for order in [31, 32] {
+                let max = low_bits::<u32>(order.into());
+                if x > max || y > max {
+                    continue;
+                }
+                let expected = [
+                    (y < max).then(|| xy2h(x, y + 1, order)),
+                    (y > 0).then(|| xy2h(x, y - 1, order)),
+                    (x > 0).then(|| xy2h(x - 1, y, order)),
+                    (x < max).then(|| xy2h(x + 1, y, order)),
+                ];
+                let h = xy2h(x, y, order);
+                assert_eq!(neighbor_keys::<u32>(h, order), expected);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "outside of the curve")]
+    fn direction_outside_of_curve() {
+        let _ = curve_direction(4u32, 0, 2);
+    }
+}
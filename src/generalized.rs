@@ -0,0 +1,319 @@
+/// Generalized hilbert curve which covers arbitrary `width` x `height` rectangles.
+///
+/// Based on the "gilbert" algorithm by Jakub Červený. Every cell of the rectangle is visited exactly once.
+/// Consecutive cells are neighbours, except for a single diagonal step which can only occur if the longer
+/// side is odd and the shorter side is even. For squares with a power of two side length the curve is
+/// identical to [`xy2h`](crate::xy2h).
+///
+/// # Examples
+///```
+/// let curve = fast_hilbert::GeneralizedHilbert::new(1920, 1080);
+/// let i = curve.index(1000, 500);
+/// assert_eq!(curve.coords(i), (1000, 500));
+///```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GeneralizedHilbert {
+    width: u32,
+    height: u32,
+}
+
+impl GeneralizedHilbert {
+    /// Create the curve for a `width` x `height` rectangle starting at the origin.
+    ///
+    /// # Panics
+    /// If `width` or `height` is zero.
+    #[must_use]
+    pub fn new(width: u32, height: u32) -> Self {
+        assert!(width > 0 && height > 0, "the rectangle must not be empty");
+        Self { width, height }
+    }
+
+    /// Width of the rectangle
+    #[must_use]
+    pub fn width(self) -> u32 {
+        self.width
+    }
+
+    /// Height of the rectangle
+    #[must_use]
+    pub fn height(self) -> u32 {
+        self.height
+    }
+
+    /// Convert from 2D coordinates to the position on the curve.
+    ///
+    /// # Panics
+    /// If the coordinates are outside of the rectangle.
+    #[must_use]
+    pub fn index(self, x: u32, y: u32) -> u64 {
+        assert!(
+            x < self.width && y < self.height,
+            "coordinates outside of the rectangle"
+        );
+        let (x, y) = (i64::from(x), i64::from(y));
+        let mut block = self.root();
+        let mut index = 0;
+        loop {
+            if let Some((dx, dy)) = block.line() {
+                return index + (dx * (x - block.x) + dy * (y - block.y)) as u64;
+            }
+            let (children, count) = block.split();
+            for child in &children[..count] {
+                if child.contains(x, y) {
+                    block = *child;
+                    break;
+                }
+                index += child.cells();
+            }
+        }
+    }
+
+    /// Convert from the position on the curve to 2D coordinates.
+    ///
+    /// # Panics
+    /// If `index` is not smaller than `width * height`.
+    #[must_use]
+    pub fn coords(self, index: u64) -> (u32, u32) {
+        let mut block = self.root();
+        assert!(index < block.cells(), "index outside of the rectangle");
+        let mut index = index;
+        loop {
+            if let Some((dx, dy)) = block.line() {
+                let offset = index as i64;
+                return (
+                    (block.x + dx * offset) as u32,
+                    (block.y + dy * offset) as u32,
+                );
+            }
+            let (children, count) = block.split();
+            for child in &children[..count] {
+                let cells = child.cells();
+                if index < cells {
+                    block = *child;
+                    break;
+                }
+                index -= cells;
+            }
+        }
+    }
+
+    /// Block of the whole rectangle with the major axis along the longer side
+    fn root(self) -> Block {
+        let (width, height) = (i64::from(self.width), i64::from(self.height));
+        if width >= height {
+            Block {
+                x: 0,
+                y: 0,
+                ax: width,
+                ay: 0,
+                bx: 0,
+                by: height,
+            }
+        } else {
+            Block {
+                x: 0,
+                y: 0,
+                ax: 0,
+                ay: height,
+                bx: width,
+                by: 0,
+            }
+        }
+    }
+}
+
+/// Sub rectangle starting at `(x, y)` which is spanned by the major axis `a` and the minor axis `b`
+#[derive(Clone, Copy)]
+struct Block {
+    x: i64,
+    y: i64,
+    ax: i64,
+    ay: i64,
+    bx: i64,
+    by: i64,
+}
+
+impl Block {
+    fn width(&self) -> i64 {
+        (self.ax + self.ay).abs()
+    }
+
+    fn height(&self) -> i64 {
+        (self.bx + self.by).abs()
+    }
+
+    fn cells(&self) -> u64 {
+        self.width() as u64 * self.height() as u64
+    }
+
+    fn contains(&self, x: i64, y: i64) -> bool {
+        let within = |v: i64, start: i64, extent: i64| {
+            if extent < 0 {
+                v <= start && v > start + extent
+            } else {
+                v >= start && v < start + extent
+            }
+        };
+        within(x, self.x, self.ax + self.bx) && within(y, self.y, self.ay + self.by)
+    }
+
+    /// Unit step of the curve if the block is a single row or column
+    fn line(&self) -> Option<(i64, i64)> {
+        if self.height() == 1 {
+            Some((self.ax.signum(), self.ay.signum()))
+        } else if self.width() == 1 {
+            Some((self.bx.signum(), self.by.signum()))
+        } else {
+            None
+        }
+    }
+
+    /// Sub blocks in curve order and their count
+    fn split(&self) -> ([Block; 3], usize) {
+        let Block {
+            x,
+            y,
+            ax,
+            ay,
+            bx,
+            by,
+        } = *self;
+        let (dax, day, dbx, dby) = (ax.signum(), ay.signum(), bx.signum(), by.signum());
+        let (mut ax2, mut ay2) = (ax.div_euclid(2), ay.div_euclid(2));
+        let (mut bx2, mut by2) = (bx.div_euclid(2), by.div_euclid(2));
+        let (width, height) = (self.width(), self.height());
+
+        if 2 * width > 3 * height {
+            // Prefer even steps
+            if (ax2 + ay2).abs() % 2 == 1 && width > 2 {
+                (ax2, ay2) = (ax2 + dax, ay2 + day);
+            }
+            // Long case: split in two parts only
+            let first = Block {
+                x,
+                y,
+                ax: ax2,
+                ay: ay2,
+                bx,
+                by,
+            };
+            let second = Block {
+                x: x + ax2,
+                y: y + ay2,
+                ax: ax - ax2,
+                ay: ay - ay2,
+                bx,
+                by,
+            };
+            return ([first, second, second], 2);
+        }
+
+        // Prefer even steps
+        if (bx2 + by2).abs() % 2 == 1 && height > 2 {
+            (bx2, by2) = (bx2 + dbx, by2 + dby);
+        }
+        // Standard case: one step up, one long horizontal, one step down
+        let up = Block {
+            x,
+            y,
+            ax: bx2,
+            ay: by2,
+            bx: ax2,
+            by: ay2,
+        };
+        let horizontal = Block {
+            x: x + bx2,
+            y: y + by2,
+            ax,
+            ay,
+            bx: bx - bx2,
+            by: by - by2,
+        };
+        let down = Block {
+            x: x + (ax - dax) + (bx2 - dbx),
+            y: y + (ay - day) + (by2 - dby),
+            ax: -bx2,
+            ay: -by2,
+            bx: -(ax - ax2),
+            by: -(ay - ay2),
+        };
+        ([up, horizontal, down], 3)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xy2h;
+
+    fn check_rectangle(width: u32, height: u32) {
+        let curve = GeneralizedHilbert::new(width, height);
+        let cells = u64::from(width) * u64::from(height);
+        let mut seen = vec![false; cells as usize];
+        let mut prev = curve.coords(0);
+        assert_eq!(prev, (0, 0));
+        let mut diagonal_steps = 0;
+        for i in 0..cells {
+            let (x, y) = curve.coords(i);
+            assert!(x < width && y < height);
+            assert_eq!(curve.index(x, y), i, "{width}x{height} ({x}, {y})");
+            assert!(!core::mem::replace(
+                &mut seen[(y * width + x) as usize],
+                true
+            ));
+            match (prev.0.abs_diff(x), prev.1.abs_diff(y)) {
+                (0, 1) | (1, 0) => {}
+                (1, 1) => diagonal_steps += 1,
+                step => assert!(i == 0, "{width}x{height} step {step:?} at {i}"),
+            }
+            prev = (x, y);
+        }
+        let (long, short) = (width.max(height), width.min(height));
+        let max_diagonal = u32::from(long % 2 == 1 && short % 2 == 0);
+        assert!(diagonal_steps <= max_diagonal, "{width}x{height}");
+    }
+
+    #[test]
+    fn all_small_rectangles() {
+        for width in 1..=24 {
+            for height in 1..=24 {
+                check_rectangle(width, height);
+            }
+        }
+        check_rectangle(192, 108);
+        check_rectangle(3, 100);
+    }
+
+    #[test]
+    fn same_as_xy2h_for_powers_of_two() {
+        for order in 0..=7u8 {
+            let side = 1u32 << order;
+            let curve = GeneralizedHilbert::new(side, side);
+            for x in 0..side {
+                for y in 0..side {
+                    assert_eq!(curve.index(x, y), xy2h(x, y, order), "order {order}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn large_rectangle() {
+        let curve = GeneralizedHilbert::new(u32::MAX, u32::MAX - 1);
+        for (x, y) in [
+            (0, 0),
+            (u32::MAX - 1, 0),
+            (12345, u32::MAX - 2),
+            (1 << 31, 1 << 30),
+        ] {
+            assert_eq!(curve.coords(curve.index(x, y)), (x, y));
+        }
+        assert_eq!(curve.index(0, 0), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "outside of the rectangle")]
+    fn outside() {
+        let _ = GeneralizedHilbert::new(3, 5).index(3, 0);
+    }
+}
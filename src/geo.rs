@@ -0,0 +1,417 @@
+//! Hilbert keys for geographic coordinates.
+//!
+//! Latitude and longitude in degrees are projected onto a plane, which is divided into `2^order` x `2^order`
+//! cells. The longitude is mapped to `x` and the latitude to `y`, so the grid of [`xy2h`] with
+//! `u32` coordinates is used and `order` can be at most 32.
+//!
+//! Only available on the `geo` feature, because the Web Mercator projection needs the float functions of `std`.
+
+use crate::{h2xy, low_bits, rect_to_ranges, xy2h, HilbertQuantizer, QuantizeError, RectRanges};
+use core::f64::consts::{FRAC_PI_4, PI};
+use core::iter::FusedIterator;
+use core::ops::RangeInclusive;
+
+/// Maximum latitude of the Web Mercator projection, which results in a square map
+pub const WEB_MERCATOR_MAX_LAT: f64 = 85.051_128_779_806_59;
+
+/// Projection from latitude and longitude onto the plane of the curve
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Projection {
+    /// Latitude and longitude are used as they are
+    #[default]
+    Equirectangular,
+    /// Spherical Mercator projection as used by web maps.
+    /// Latitudes beyond [`WEB_MERCATOR_MAX_LAT`] are clamped.
+    WebMercator,
+}
+
+impl Projection {
+    fn quantizer(self, order: u8) -> Result<HilbertQuantizer, QuantizeError> {
+        match self {
+            Projection::Equirectangular => HilbertQuantizer::new(-180.0, -90.0, 180.0, 90.0, order),
+            Projection::WebMercator => HilbertQuantizer::new(-180.0, -PI, 180.0, PI, order),
+        }
+    }
+
+    fn project_lat(self, lat: f64) -> f64 {
+        match self {
+            Projection::Equirectangular => lat,
+            Projection::WebMercator => {
+                let lat = lat.clamp(-WEB_MERCATOR_MAX_LAT, WEB_MERCATOR_MAX_LAT);
+                (FRAC_PI_4 + lat.to_radians() / 2.0).tan().ln()
+            }
+        }
+    }
+
+    fn unproject_lat(self, y: f64) -> f64 {
+        match self {
+            Projection::Equirectangular => y,
+            Projection::WebMercator => y.sinh().atan().to_degrees(),
+        }
+    }
+}
+
+/// Latitude and longitude bounds in degrees
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LatLonBox {
+    pub min_lat: f64,
+    pub min_lon: f64,
+    pub max_lat: f64,
+    pub max_lon: f64,
+}
+
+/// Validate a coordinate and project it onto the plane
+fn project(lat: f64, lon: f64, projection: Projection) -> Result<(f64, f64), QuantizeError> {
+    if lat.is_nan() || lon.is_nan() {
+        return Err(QuantizeError::NotANumber);
+    }
+    if !(-90.0..=90.0).contains(&lat) || !(-180.0..=180.0).contains(&lon) {
+        return Err(QuantizeError::OutOfBounds { x: lon, y: lat });
+    }
+    Ok((lon, projection.project_lat(lat)))
+}
+
+/// Convert latitude and longitude in degrees to a hilbert index.
+///
+/// # Arguments
+/// * `lat` - Latitude within `-90..=90`
+/// * `lon` - Longitude within `-180..=180`
+/// * `order` - The hilbert curve order, at most 32
+/// * `projection` - Projection onto the plane of the curve
+///
+/// # Errors
+/// [`QuantizeError::InvalidOrder`] if `order` exceeds 32, [`QuantizeError::NotANumber`] if a coordinate is
+/// NaN and [`QuantizeError::OutOfBounds`] if a coordinate is outside of its range.
+///
+/// # Examples
+///```
+/// use fast_hilbert::geo::{latlon_to_h, Projection};
+///
+/// let h = latlon_to_h(52.52, 13.40, 16, Projection::WebMercator).unwrap();
+/// assert!(h < 1 << 32);
+///```
+pub fn latlon_to_h(
+    lat: f64,
+    lon: f64,
+    order: u8,
+    projection: Projection,
+) -> Result<u64, QuantizeError> {
+    let quantizer = projection.quantizer(order)?;
+    let (x, y) = project(lat, lon, projection)?;
+    let (x, y) = quantizer.cell_of(x, y);
+    Ok(xy2h(x, y, order))
+}
+
+/// Bounds of the cell of a hilbert index in degrees.
+///
+/// # Arguments
+/// * `h` - Coordinate in 1D hilbert space
+/// * `order` - The hilbert curve order, at most 32
+/// * `projection` - Projection onto the plane of the curve
+///
+/// # Panics
+/// If `order` exceeds 32 or `h` exceeds the indices of the order.
+///
+/// # Examples
+///```
+/// use fast_hilbert::geo::{h_to_latlon_cell, latlon_to_h, Projection};
+///
+/// let h = latlon_to_h(52.52, 13.40, 16, Projection::Equirectangular).unwrap();
+/// let cell = h_to_latlon_cell(h, 16, Projection::Equirectangular);
+/// assert!(cell.min_lat <= 52.52 && 52.52 < cell.max_lat);
+/// assert!(cell.min_lon <= 13.40 && 13.40 < cell.max_lon);
+///```
+#[must_use]
+pub fn h_to_latlon_cell(h: u64, order: u8, projection: Projection) -> LatLonBox {
+    let quantizer = match projection.quantizer(order) {
+        Ok(quantizer) => quantizer,
+        Err(err) => panic!("{err}"),
+    };
+    assert!(
+        h <= low_bits(u32::from(order) << 1),
+        "index exceeds the order"
+    );
+    let (x, y) = h2xy::<u32>(h, order);
+    let (min_lon, min_y, max_lon, max_y) = quantizer.cell_bounds(x, y);
+    LatLonBox {
+        min_lat: projection.unproject_lat(min_y),
+        min_lon,
+        max_lat: projection.unproject_lat(max_y),
+        max_lon,
+    }
+}
+
+/// Iterator over the hilbert index ranges which cover a latitude and longitude bounding box.
+///
+/// Created by [`bbox_to_ranges`]. If the box crosses the antimeridian it is split into an eastern and a
+/// western part. The ranges of each part are sorted and the parts do not overlap.
+#[derive(Debug, Clone)]
+pub struct LatLonRanges {
+    east: RectRanges<u32>,
+    west: Option<RectRanges<u32>>,
+}
+
+impl Iterator for LatLonRanges {
+    type Item = RangeInclusive<u64>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.east.next().or_else(|| self.west.as_mut()?.next())
+    }
+}
+
+impl FusedIterator for LatLonRanges {}
+
+/// Decompose a latitude and longitude bounding box into hilbert index ranges.
+///
+/// A `min_lon` greater than `max_lon` describes a box which crosses the antimeridian. It is covered by the two
+/// boxes from `min_lon` to 180 and from -180 to `max_lon`, whose ranges are returned one after the other.
+///
+/// # Arguments
+/// * `bbox` - Latitude and longitude bounds in degrees
+/// * `order` - The hilbert curve order, at most 32
+/// * `projection` - Projection onto the plane of the curve
+///
+/// # Errors
+/// [`QuantizeError::InvalidOrder`] if `order` exceeds 32, [`QuantizeError::NotANumber`] if a bound is
+/// NaN, [`QuantizeError::OutOfBounds`] if a bound is outside of its range and [`QuantizeError::InvalidBounds`]
+/// if `min_lat` is greater than `max_lat`.
+///
+/// # Examples
+///```
+/// use fast_hilbert::geo::{bbox_to_ranges, latlon_to_h, LatLonBox, Projection};
+///
+/// let bbox = LatLonBox { min_lat: -10.0, min_lon: 170.0, max_lat: 10.0, max_lon: -170.0 };
+/// let ranges: Vec<_> = bbox_to_ranges(bbox, 8, Projection::Equirectangular).unwrap().collect();
+/// let h = latlon_to_h(0.0, 179.0, 8, Projection::Equirectangular).unwrap();
+/// assert!(ranges.iter().any(|range| range.contains(&h)));
+/// let h = latlon_to_h(0.0, -179.0, 8, Projection::Equirectangular).unwrap();
+/// assert!(ranges.iter().any(|range| range.contains(&h)));
+///```
+pub fn bbox_to_ranges(
+    bbox: LatLonBox,
+    order: u8,
+    projection: Projection,
+) -> Result<LatLonRanges, QuantizeError> {
+    let quantizer = projection.quantizer(order)?;
+    let (min_lon, min_y) = project(bbox.min_lat, bbox.min_lon, projection)?;
+    let (max_lon, max_y) = project(bbox.max_lat, bbox.max_lon, projection)?;
+    if bbox.min_lat > bbox.max_lat {
+        return Err(QuantizeError::InvalidBounds {
+            min_x: bbox.min_lon,
+            min_y: bbox.min_lat,
+            max_x: bbox.max_lon,
+            max_y: bbox.max_lat,
+        });
+    }
+    let (x_min, y_min) = quantizer.cell_of(min_lon, min_y);
+    let (x_max, y_max) = quantizer.cell_of(max_lon, max_y);
+
+    let ranges = if min_lon <= max_lon {
+        LatLonRanges {
+            east: rect_to_ranges(x_min, y_min, x_max, y_max, order),
+            west: None,
+        }
+    } else if x_min <= x_max {
+        // Both parts share a column, so the whole width is covered
+        LatLonRanges {
+            east: rect_to_ranges(0, y_min, u32::MAX, y_max, order),
+            west: None,
+        }
+    } else {
+        LatLonRanges {
+            east: rect_to_ranges(x_min, y_min, u32::MAX, y_max, order),
+            west: Some(rect_to_ranges(0, y_min, x_max, y_max, order)),
+        }
+    };
+    Ok(ranges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROJECTIONS: [Projection; 2] = [Projection::Equirectangular, Projection::WebMercator];
+
+    #[test]
+    fn latlon_cell_contains_point() {
+        for projection in PROJECTIONS {
+            for order in [1, 8, 16, 32] {
+                for (lat, lon) in [
+                    (0.0, 0.0),
+                    (52.52, 13.405),
+                    (-33.87, 151.21),
+                    (-85.0, -180.0),
+                    (40.7, -74.0),
+                ] {
+                    let h = latlon_to_h(lat, lon, order, projection).unwrap();
+                    let cell = h_to_latlon_cell(h, order, projection);
+                    assert!(
+                        cell.min_lat <= lat && lat <= cell.max_lat,
+                        "{projection:?} {order} {cell:?}"
+                    );
+                    assert!(
+                        cell.min_lon <= lon && lon <= cell.max_lon,
+                        "{projection:?} {order} {cell:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn latlon_extremes() {
+        let corner = h_to_latlon_cell(0, 4, Projection::Equirectangular);
+        assert_eq!(
+            corner,
+            LatLonBox {
+                min_lat: -90.0,
+                min_lon: -180.0,
+                max_lat: -78.75,
+                max_lon: -157.5
+            }
+        );
+        let corner = h_to_latlon_cell(0, 4, Projection::WebMercator);
+        assert!((corner.min_lat + WEB_MERCATOR_MAX_LAT).abs() < 1e-9);
+
+        // Poles are clamped onto the border of the mercator projection
+        let pole = latlon_to_h(90.0, 0.0, 10, Projection::WebMercator);
+        assert_eq!(
+            pole,
+            latlon_to_h(WEB_MERCATOR_MAX_LAT, 0.0, 10, Projection::WebMercator)
+        );
+        assert_eq!(
+            latlon_to_h(90.0, 180.0, 10, Projection::Equirectangular),
+            Ok(xy2h(1023u32, 1023, 10))
+        );
+
+        assert_eq!(
+            latlon_to_h(f64::NAN, 0.0, 10, Projection::Equirectangular),
+            Err(QuantizeError::NotANumber)
+        );
+        assert_eq!(
+            latlon_to_h(0.0, 180.5, 10, Projection::Equirectangular),
+            Err(QuantizeError::OutOfBounds { x: 180.5, y: 0.0 })
+        );
+        assert_eq!(
+            latlon_to_h(0.0, 0.0, 33, Projection::Equirectangular),
+            Err(QuantizeError::InvalidOrder { order: 33 })
+        );
+    }
+
+    /// Sorted indices of all cells which are covered by the ranges of the box
+    fn covered(bbox: LatLonBox, order: u8, projection: Projection) -> Vec<u64> {
+        let mut covered: Vec<u64> = bbox_to_ranges(bbox, order, projection)
+            .unwrap()
+            .flatten()
+            .collect();
+        covered.sort_unstable();
+        covered
+    }
+
+    #[test]
+    fn bbox_ranges() {
+        let order = 5;
+        for projection in PROJECTIONS {
+            let bbox = LatLonBox {
+                min_lat: -20.0,
+                min_lon: 10.0,
+                max_lat: 45.0,
+                max_lon: 100.0,
+            };
+            let covered = covered(bbox, order, projection);
+            for h in 0..1 << (2 * order) {
+                let cell = h_to_latlon_cell(h, order, projection);
+                let intersects = cell.max_lat > bbox.min_lat
+                    && cell.min_lat <= bbox.max_lat
+                    && cell.max_lon > bbox.min_lon
+                    && cell.min_lon <= bbox.max_lon;
+                assert_eq!(
+                    covered.binary_search(&h).is_ok(),
+                    intersects,
+                    "{projection:?} {cell:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn bbox_antimeridian() {
+        let order = 5;
+        for projection in PROJECTIONS {
+            let bbox = LatLonBox {
+                min_lat: -30.0,
+                min_lon: 150.0,
+                max_lat: 30.0,
+                max_lon: -160.0,
+            };
+            let ranges: Vec<_> = bbox_to_ranges(bbox, order, projection).unwrap().collect();
+            let covered = covered(bbox, order, projection);
+            assert!(
+                covered.windows(2).all(|pair| pair[0] < pair[1]),
+                "parts overlap"
+            );
+            assert_eq!(
+                ranges.iter().map(|r| r.end() - r.start() + 1).sum::<u64>(),
+                covered.len() as u64
+            );
+
+            let east = LatLonBox {
+                max_lon: 180.0,
+                ..bbox
+            };
+            let west = LatLonBox {
+                min_lon: -180.0,
+                ..bbox
+            };
+            let mut expected = [
+                self::covered(east, order, projection),
+                self::covered(west, order, projection),
+            ]
+            .concat();
+            expected.sort_unstable();
+            assert_eq!(covered, expected);
+        }
+
+        // Parts which meet in the same column cover the whole width
+        let bbox = LatLonBox {
+            min_lat: 0.0,
+            min_lon: 1.0,
+            max_lat: 1.0,
+            max_lon: 0.5,
+        };
+        let all = LatLonBox {
+            min_lat: 0.0,
+            min_lon: -180.0,
+            max_lat: 1.0,
+            max_lon: 180.0,
+        };
+        assert_eq!(
+            covered(bbox, 2, Projection::Equirectangular),
+            covered(all, 2, Projection::Equirectangular)
+        );
+    }
+
+    #[test]
+    fn bbox_errors() {
+        let bbox = LatLonBox {
+            min_lat: 10.0,
+            min_lon: 0.0,
+            max_lat: -10.0,
+            max_lon: 1.0,
+        };
+        assert!(matches!(
+            bbox_to_ranges(bbox, 8, Projection::Equirectangular),
+            Err(QuantizeError::InvalidBounds { .. })
+        ));
+        let bbox = LatLonBox {
+            min_lat: -91.0,
+            min_lon: 0.0,
+            max_lat: 10.0,
+            max_lon: 1.0,
+        };
+        assert!(matches!(
+            bbox_to_ranges(bbox, 8, Projection::Equirectangular),
+            Err(QuantizeError::OutOfBounds { .. })
+        ));
+    }
+}
@@ -0,0 +1,333 @@
+use crate::{assert_order, low_bits, UnsignedBase};
+
+/// Unsigned coordinate type which has a key type that is wide enough for three coordinates
+pub trait Unsigned3D: UnsignedBase
+where
+    Self::Key3D: UnsignedBase,
+{
+    type Key3D; // At least three times the bits of the coordinate type
+}
+
+impl Unsigned3D for u32 {
+    type Key3D = u128;
+}
+impl Unsigned3D for u16 {
+    type Key3D = u64;
+}
+impl Unsigned3D for u8 {
+    type Key3D = u32;
+}
+
+// Single level of the 3D state machine from 3D to 1D. There are 12 states.
+// SSSSXYZ => SSSSHHH
+//  7 bit  =>  7 bit
+const LUT3D_SXYZ2SH: [u8; 96] = [
+    8, 23, 25, 38, 43, 44, 26, 37, //
+    24, 51, 63, 52, 1, 2, 70, 69, //
+    76, 39, 75, 80, 5, 6, 66, 65, //
+    0, 9, 83, 10, 95, 78, 84, 77, //
+    22, 7, 21, 60, 49, 88, 50, 59, //
+    58, 85, 3, 4, 57, 86, 72, 55, //
+    90, 89, 45, 46, 11, 32, 12, 87, //
+    36, 13, 71, 14, 35, 74, 40, 73, //
+    62, 81, 15, 16, 61, 82, 92, 91, //
+    94, 93, 41, 42, 31, 20, 56, 19, //
+    18, 27, 17, 64, 53, 28, 54, 47, //
+    68, 67, 29, 34, 79, 48, 30, 33, //
+];
+
+// Single level of the 3D state machine from 1D to 3D
+// SSSSHHH => SSSSXYZ
+//  7 bit  =>  7 bit
+const LUT3D_SH2SXYZ: [u8; 96] = [
+    8, 26, 30, 44, 45, 39, 35, 17, //
+    24, 4, 5, 49, 51, 71, 70, 58, //
+    83, 71, 70, 74, 72, 4, 5, 33, //
+    0, 9, 11, 82, 86, 79, 77, 92, //
+    93, 52, 54, 63, 59, 18, 16, 1, //
+    78, 60, 56, 2, 3, 81, 85, 55, //
+    37, 89, 88, 12, 14, 42, 43, 87, //
+    46, 79, 77, 36, 32, 9, 11, 66, //
+    19, 81, 85, 95, 94, 60, 56, 10, //
+    62, 42, 43, 23, 21, 89, 88, 28, //
+    67, 18, 16, 25, 29, 52, 54, 47, //
+    53, 39, 35, 65, 64, 26, 30, 76, //
+];
+
+// Mapping from state and two levels of coordinates to the next state and two hilbert digits
+// SSSSXXYYZZ => SSSSHHHHHH
+//     10 bit => 10 bit
+static LUT3D_2: [u16; 768] = gen_lut3d_2(&LUT3D_SXYZ2SH);
+
+// Mapping from state and two hilbert digits to the next state and two levels of coordinates
+// SSSSHHHHHH => SSSSXXYYZZ
+//     10 bit => 10 bit
+static LUT3D_2_REV: [u16; 768] = gen_lut3d_2_rev(&LUT3D_SH2SXYZ);
+
+/// Combine two levels of [`LUT3D_SXYZ2SH`] into a single lookup
+const fn gen_lut3d_2(lut: &[u8; 96]) -> [u16; 768] {
+    let mut result = [0; 768];
+    let mut input = 0;
+    while input < result.len() {
+        let (xx, yy, zz) = ((input >> 4) & 3, (input >> 2) & 3, input & 3);
+        let upper = (xx >> 1) << 2 | (yy >> 1) << 1 | zz >> 1;
+        let lower = (xx & 1) << 2 | (yy & 1) << 1 | zz & 1;
+        let r_upper = lut[(input >> 6) << 3 | upper] as usize;
+        let r_lower = lut[(r_upper >> 3) << 3 | lower] as usize;
+        result[input] = ((r_lower >> 3) << 6 | (r_upper & 7) << 3 | r_lower & 7) as u16;
+        input += 1;
+    }
+    result
+}
+
+/// Combine two levels of [`LUT3D_SH2SXYZ`] into a single lookup
+const fn gen_lut3d_2_rev(lut: &[u8; 96]) -> [u16; 768] {
+    let mut result = [0; 768];
+    let mut input = 0;
+    while input < result.len() {
+        let r_upper = lut[(input >> 6) << 3 | (input >> 3) & 7] as usize;
+        let r_lower = lut[(r_upper >> 3) << 3 | input & 7] as usize;
+        let xx = (r_upper >> 2 & 1) << 1 | r_lower >> 2 & 1;
+        let yy = (r_upper >> 1 & 1) << 1 | r_lower >> 1 & 1;
+        let zz = (r_upper & 1) << 1 | r_lower & 1;
+        result[input] = ((r_lower >> 3) << 6 | xx << 4 | yy << 2 | zz) as u16;
+        input += 1;
+    }
+    result
+}
+
+/// Number of levels to compute. Three leading zero levels lead back to the initial state,
+/// so only the lowest levels with the same remainder as `order` are needed.
+#[inline]
+fn lowest_levels(needed: u32, order: u8) -> u32 {
+    needed + (u32::from(order) % 3 + 3 - needed % 3) % 3
+}
+
+/// The `count` bits of `value` starting at bit `shift`, which may exceed the type
+#[inline]
+fn bits_at<U: UnsignedBase>(value: U, shift: u32, count: u8) -> usize {
+    if shift >= (size_of::<U>() << 3) as u32 {
+        0
+    } else {
+        ((value >> shift as usize) & ((1 << count) - 1).into()).as_usize()
+    }
+}
+
+/// Convert from 3D to 1D hilbert space.
+///
+/// The key type has three times the capacity of the coordinate type, rounded up to the next unsigned integer.
+/// For example (u16, u16, u16) => u64 and (u32, u32, u32) => u128.
+///
+/// # Arguments
+/// * `x` - Coordinate in 3D space
+/// * `y` - Coordinate in 3D space
+/// * `z` - Coordinate in 3D space
+/// * `order` - The hilbert curve order
+///
+/// # Panics
+/// If `order` exceeds the number of bits of `T`.
+///
+/// # Examples
+///```
+/// let hilbert = fast_hilbert::xyz2h(0u16, 0, 1, 1);
+/// assert_eq!(hilbert, 7u64);
+///```
+pub fn xyz2h<T: Unsigned3D>(x: T, y: T, z: T, order: u8) -> T::Key3D {
+    assert_order::<T>(order);
+    let coor_bits = (size_of::<T>() << 3) as u32;
+    let needed = coor_bits - (x | y | z).leading_zeros();
+    let mut level = lowest_levels(needed, order);
+
+    let mut state = 0;
+    let mut result = T::Key3D::ZERO;
+    if level & 1 == 1 {
+        level -= 1;
+        let index = state << 3
+            | bits_at(x, level, 1) << 2
+            | bits_at(y, level, 1) << 1
+            | bits_at(z, level, 1);
+        let r = LUT3D_SXYZ2SH[index];
+        state = usize::from(r >> 3);
+        result = (r & 7).into();
+    }
+    while level > 0 {
+        level -= 2;
+        let index = state << 6
+            | bits_at(x, level, 2) << 4
+            | bits_at(y, level, 2) << 2
+            | bits_at(z, level, 2);
+        let r = LUT3D_2[index];
+        state = usize::from(r >> 6);
+        result = result << 6usize | T::Key3D::from((r & 63) as u8);
+    }
+    result
+}
+
+/// Convert from 1D hilbert space to 3D coordinates
+///
+/// # Arguments
+/// * `h`     - Coordinate in 1D hilbert space
+/// * `order` - Hilbert curve order
+///
+/// # Panics
+/// If `order` exceeds the number of bits of `T` or `h` exceeds the indices of `order`.
+///
+/// # Examples
+///```
+/// let (x, y, z) = fast_hilbert::h2xyz::<u16>(7, 1);
+/// assert_eq!((x, y, z), (0, 0, 1));
+///```
+pub fn h2xyz<T: Unsigned3D>(h: T::Key3D, order: u8) -> (T, T, T) {
+    assert_order::<T>(order);
+    assert!(
+        h <= low_bits(u32::from(order) * 3),
+        "index {h:?} exceeds the indices of order {order}"
+    );
+    let key_bits = (size_of::<T::Key3D>() << 3) as u32;
+    let needed = (key_bits - h.leading_zeros()).div_ceil(3);
+    let mut level = lowest_levels(needed, order);
+
+    let mut state = 0;
+    let (mut x, mut y, mut z) = (T::ZERO, T::ZERO, T::ZERO);
+    if level & 1 == 1 {
+        level -= 1;
+        let lookup = LUT3D_SH2SXYZ[state << 3 | bits_at(h, level * 3, 3)];
+        state = usize::from(lookup >> 3);
+        x = T::from(lookup >> 2 & 1);
+        y = T::from(lookup >> 1 & 1);
+        z = T::from(lookup & 1);
+    }
+    while level > 0 {
+        level -= 2;
+        let lookup = LUT3D_2_REV[state << 6 | bits_at(h, level * 3, 6)];
+        state = usize::from(lookup >> 6);
+        x = x << 2usize | T::from((lookup >> 4 & 3) as u8);
+        y = y << 2usize | T::from((lookup >> 2 & 3) as u8);
+        z = z << 2usize | T::from((lookup & 3) as u8);
+    }
+    (x, y, z)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xorshift;
+
+    /// Derive the single level state machine from the entry point `e` and direction `d` of every sub-cube
+    /// as described in "Compact Hilbert Indices" by Chris Hamilton.
+    #[test]
+    fn gen_lut3d_sxyz2sh() {
+        const N: u32 = 3;
+        let rotate_right = |b: u32, r: u32| ((b >> (r % N)) | (b << (N - r % N))) & 7;
+        let rotate_left = |b: u32, r: u32| ((b << (r % N)) | (b >> (N - r % N))) & 7;
+        let gray = |i: u32| i ^ (i >> 1);
+        let gray_inverse = |g: u32| g ^ (g >> 1) ^ (g >> 2);
+        let entry = |w: u32| if w == 0 { 0 } else { gray(2 * ((w - 1) / 2)) };
+        let direction = |w: u32| match w {
+            0 => 0,
+            w if w % 2 == 0 => (w - 1).trailing_ones() % N,
+            w => w.trailing_ones() % N,
+        };
+
+        let mut states = vec![(0, 0)];
+        let mut lut = Vec::new();
+        let mut i = 0;
+        while i < states.len() {
+            let (e, d) = states[i];
+            for xyz in 0..8 {
+                let w = gray_inverse(rotate_right(xyz ^ e, d + 1));
+                let next = (e ^ rotate_left(entry(w), d + 1), (d + direction(w) + 1) % N);
+                let next_state = states.iter().position(|&s| s == next).unwrap_or_else(|| {
+                    states.push(next);
+                    states.len() - 1
+                });
+                lut.push((next_state << 3) as u8 | w as u8);
+            }
+            i += 1;
+        }
+        assert_eq!(lut, LUT3D_SXYZ2SH);
+
+        let mut rev = [0; 96];
+        for (input, r) in lut.iter().enumerate() {
+            rev[input & !7 | usize::from(r & 7)] = r & !7 | (input & 7) as u8;
+        }
+        assert_eq!(rev, LUT3D_SH2SXYZ);
+    }
+
+    #[test]
+    fn xyz2h_and_rev() {
+        for order in 1..=5u8 {
+            let n = 1u16 << order;
+            for x in 0..n {
+                for y in 0..n {
+                    for z in 0..n {
+                        let h = xyz2h(x, y, z, order);
+                        assert!(h < 1 << (3 * order));
+                        assert_eq!(h2xyz::<u16>(h, order), (x, y, z));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn adjacent() {
+        for order in 1..=6u8 {
+            let mut prev = h2xyz::<u8>(0, order);
+            assert_eq!(prev, (0, 0, 0));
+            for h in 1..1u32 << (3 * order) {
+                let next = h2xyz::<u8>(h, order);
+                let distance =
+                    prev.0.abs_diff(next.0) + prev.1.abs_diff(next.1) + prev.2.abs_diff(next.2);
+                assert_eq!(distance, 1, "order {order} h {h}");
+                prev = next;
+            }
+        }
+    }
+
+    #[test]
+    fn leading_zero_levels() {
+        for order in 1..=7 {
+            for (x, y, z) in [(1u32, 0, 0), (3, 2, 1), (0, 7, 5)] {
+                assert_eq!(xyz2h(x, y, z, order), xyz2h(x, y, z, order + 3));
+                assert_eq!(xyz2h(x, y, z, order), xyz2h(x, y, z, order + 24));
+            }
+        }
+    }
+
+    #[test]
+    fn full_order() {
+        let mut next = xorshift(0x853c_49e6_748f_ea9b);
+        for _ in 0..1000 {
+            let (x, y, z) = (next() as u32, next() as u32, next() as u32);
+            for order in [30, 31, 32] {
+                let mask = u32::MAX >> (32 - order);
+                let (x, y, z) = (x & mask, y & mask, z & mask);
+                assert_eq!(h2xyz::<u32>(xyz2h(x, y, z, order), order), (x, y, z));
+            }
+            let (x, y, z) = (x as u16, y as u16, z as u16);
+            assert_eq!(h2xyz::<u16>(xyz2h(x, y, z, 16), 16), (x, y, z));
+            let (x, y, z) = (x as u8, y as u8, z as u8);
+            assert_eq!(h2xyz::<u8>(xyz2h(x, y, z, 8), 8), (x, y, z));
+        }
+        assert_eq!(
+            h2xyz::<u32>(xyz2h(u32::MAX, u32::MAX, u32::MAX, 32), 32),
+            (u32::MAX, u32::MAX, u32::MAX)
+        );
+        assert_eq!(h2xyz::<u32>((1 << 96) - 1, 32), (0, 0, u32::MAX));
+    }
+
+    #[test]
+    fn out_of_range_panics() {
+        use std::panic::catch_unwind;
+        assert!(catch_unwind(|| h2xyz::<u8>(1 << 24, 8)).is_err());
+        assert!(catch_unwind(|| h2xyz::<u16>(1 << 50, 16)).is_err());
+        assert!(catch_unwind(|| h2xyz::<u16>(0, 17)).is_err());
+        assert!(catch_unwind(|| xyz2h(0u8, 0, 0, 9)).is_err());
+        // The largest index of each curve is accepted
+        let (x, y, z) = h2xyz::<u8>((1 << 24) - 1, 8);
+        assert_eq!(xyz2h(x, y, z, 8), (1 << 24) - 1);
+        let (x, y, z) = h2xyz::<u16>((1 << 48) - 1, 16);
+        assert_eq!(xyz2h(x, y, z, 16), (1 << 48) - 1);
+    }
+}
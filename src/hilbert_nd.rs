@@ -0,0 +1,255 @@
+use crate::{assert_order, h2xy, low_bits, xy2h, Unsigned};
+
+/// Convert from D-dimensional space to 1D hilbert space.
+///
+/// Uses the transpose based algorithm from "Programming the Hilbert curve" by John Skilling.
+/// For `D == 2` the result is identical to [`xy2h`], for other dimensions the curve starts at
+/// the origin as well.
+///
+/// # Arguments
+/// * `coords` - Coordinates in D-dimensional space
+/// * `order` - The hilbert curve order
+///
+/// # Panics
+/// If `D` is zero, `D * order` exceeds the 128 bits of the key, `order` exceeds the bits of `T` or a
+/// coordinate is outside of the curve.
+///
+/// # Examples
+///```
+/// let h = fast_hilbert::encode([1u16, 0], 1);
+/// assert_eq!(h, fast_hilbert::xy2h(1u16, 0, 1).into());
+/// let h = fast_hilbert::encode([0u8, 0, 0, 1], 1);
+/// assert_eq!(h, 1);
+///```
+pub fn encode<const D: usize, T>(coords: [T; D], order: u8) -> u128
+where
+    T: Unsigned + Into<u64>,
+{
+    check_dimensions::<D, T>(order);
+    let max: T = low_bits(order.into());
+    assert!(
+        coords.iter().all(|&coord| coord <= max),
+        "coordinates {coords:?} are outside of the curve with order {order}"
+    );
+    if D == 2 {
+        return xy2h::<u64>(coords[0].into(), coords[1].into(), order);
+    }
+
+    let mut transposed = coords.map(Into::into);
+    axes_to_transpose(&mut transposed, order);
+    let mut h = 0;
+    for level in (0..order).rev() {
+        for axis in transposed {
+            h = h << 1 | u128::from(axis >> level & 1);
+        }
+    }
+    h
+}
+
+/// Convert from 1D hilbert space to D-dimensional coordinates.
+///
+/// Inverse of [`encode`].
+///
+/// # Arguments
+/// * `h` - Coordinate in 1D hilbert space
+/// * `order` - The hilbert curve order
+///
+/// # Panics
+/// If `D` is zero, `D * order` exceeds the 128 bits of the key, `order` exceeds the bits of `T` or `h`
+/// exceeds the indices of the curve.
+///
+/// # Examples
+///```
+/// let coords: [u8; 4] = fast_hilbert::decode(1, 1);
+/// assert_eq!(coords, [0, 0, 0, 1]);
+///```
+#[must_use]
+pub fn decode<const D: usize, T>(h: u128, order: u8) -> [T; D]
+where
+    T: Unsigned + Into<u64>,
+{
+    check_dimensions::<D, T>(order);
+    assert!(
+        h <= low_bits(u32::from(order) * D as u32),
+        "index {h} exceeds the curve with dimension {D} and order {order}"
+    );
+    let mut transposed = [0u64; D];
+    if D == 2 {
+        let (x, y) = h2xy::<u64>(h, order);
+        (transposed[0], transposed[1]) = (x, y);
+    } else {
+        let mut bit = u32::from(order) * D as u32;
+        for level in (0..order).rev() {
+            for axis in &mut transposed {
+                bit -= 1;
+                *axis |= ((h >> bit) as u64 & 1) << level;
+            }
+        }
+        transpose_to_axes(&mut transposed, order);
+    }
+    transposed.map(from_u64)
+}
+
+/// Convert a value with at most the bits of `T` byte by byte
+fn from_u64<T: Unsigned>(value: u64) -> T {
+    let mut out = T::ZERO;
+    for shift in (0..size_of::<T>()).map(|byte| byte << 3) {
+        out |= T::from((value >> shift) as u8) << shift;
+    }
+    out
+}
+
+fn check_dimensions<const D: usize, T: Unsigned>(order: u8) {
+    assert!(D > 0, "at least one dimension is required");
+    assert_order::<T>(order);
+    assert!(
+        D * usize::from(order) <= 128,
+        "key with dimension * order bits exceeds 128 bits"
+    );
+}
+
+/// Transform coordinates into the transposed hilbert index in place.
+fn axes_to_transpose(axes: &mut [u64], order: u8) {
+    let Some(top) = order.checked_sub(1) else {
+        return;
+    };
+    let dims = axes.len();
+
+    // Inverse undo excess work
+    let mut q = 1u64 << top;
+    while q > 1 {
+        let p = q - 1;
+        for i in 0..dims {
+            if axes[i] & q == 0 {
+                let flip = (axes[0] ^ axes[i]) & p;
+                axes[0] ^= flip;
+                axes[i] ^= flip;
+            } else {
+                axes[0] ^= p;
+            }
+        }
+        q >>= 1;
+    }
+
+    // Gray encode
+    for i in 1..dims {
+        axes[i] ^= axes[i - 1];
+    }
+    let mut flip = 0;
+    let mut q = 1u64 << top;
+    while q > 1 {
+        if axes[dims - 1] & q != 0 {
+            flip ^= q - 1;
+        }
+        q >>= 1;
+    }
+    for axis in axes {
+        *axis ^= flip;
+    }
+}
+
+/// Transform the transposed hilbert index into coordinates in place.
+fn transpose_to_axes(axes: &mut [u64], order: u8) {
+    if order == 0 {
+        return;
+    }
+    let dims = axes.len();
+
+    // Gray decode
+    let flip = axes[dims - 1] >> 1;
+    for i in (1..dims).rev() {
+        axes[i] ^= axes[i - 1];
+    }
+    axes[0] ^= flip;
+
+    // Undo excess work
+    for level in 1..order {
+        let q = 1u64 << level;
+        let p = q - 1;
+        for i in (0..dims).rev() {
+            if axes[i] & q == 0 {
+                let flip = (axes[0] ^ axes[i]) & p;
+                axes[0] ^= flip;
+                axes[i] ^= flip;
+            } else {
+                axes[0] ^= p;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xorshift;
+
+    fn check_adjacent<const D: usize>(order: u8) {
+        let mut prev = [0u8; D];
+        assert_eq!(decode::<D, u8>(0, order), prev);
+        for h in 1..1u128 << (D * usize::from(order)) {
+            let next: [u8; D] = decode(h, order);
+            let distance: u8 = prev.iter().zip(next).map(|(a, b)| a.abs_diff(b)).sum();
+            assert_eq!(distance, 1, "dimension {D} order {order} h {h}");
+            assert_eq!(encode(next, order), h);
+            prev = next;
+        }
+    }
+
+    #[test]
+    fn adjacent_and_rev() {
+        for order in 1..=4 {
+            check_adjacent::<1>(order);
+            check_adjacent::<2>(order);
+            check_adjacent::<3>(order);
+            check_adjacent::<4>(order);
+        }
+        check_adjacent::<5>(2);
+        check_adjacent::<6>(2);
+        check_adjacent::<8>(2);
+    }
+
+    #[test]
+    fn two_dimensions_match_xy2h() {
+        let mut next = xorshift(0x2545_f491_4f6c_dd1d);
+        for _ in 0..1000 {
+            let (x, y) = (next(), next());
+            for order in [1, 7, 63, 64] {
+                let mask = u64::MAX >> (64 - order);
+                let (x, y) = (x & mask, y & mask);
+                assert_eq!(encode([x, y], order), xy2h(x, y, order));
+                assert_eq!(decode::<2, u64>(xy2h(x, y, order), order), [x, y]);
+            }
+            let (x, y) = (x as u32, y as u32);
+            assert_eq!(encode([x, y], 32), u128::from(xy2h(x, y, 32)));
+        }
+    }
+
+    #[test]
+    fn full_key() {
+        let max = [u16::MAX; 8];
+        assert_eq!(decode::<8, u16>(encode(max, 16), 16), max);
+        let coords = [u32::MAX, 0, 1 << 31, 12345];
+        assert_eq!(decode::<4, u32>(encode(coords, 32), 32), coords);
+        assert_eq!(encode::<4, u32>(decode(u128::MAX, 32), 32), u128::MAX);
+    }
+
+    #[test]
+    fn out_of_range_panics_for_every_dimension() {
+        use std::panic::catch_unwind;
+        assert!(catch_unwind(|| decode::<2, u8>(1 << 20, 4)).is_err());
+        assert!(catch_unwind(|| decode::<3, u8>(1 << 20, 4)).is_err());
+        assert!(catch_unwind(|| decode::<1, u8>(300, 8)).is_err());
+        assert!(catch_unwind(|| encode([200u8, 3], 2)).is_err());
+        assert!(catch_unwind(|| encode([200u8, 3, 1], 2)).is_err());
+        // The largest index and coordinates of each curve are accepted
+        assert_eq!(decode::<1, u8>(255, 8), [255]);
+        assert_eq!(decode::<3, u8>(encode([3u8, 3, 3], 2), 2), [3, 3, 3]);
+        assert_eq!(decode::<2, u8>(encode([3u8, 0], 2), 2), [3, 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds 128 bits")]
+    fn key_overflow() {
+        encode([0u32; 5], 26);
+    }
+}
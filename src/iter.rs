@@ -0,0 +1,434 @@
+use crate::{
+    assert_order, h2xy, low_bits, max_order, Unsigned, UnsignedBase, UnsignedCurve, UnsignedOps,
+    LUT_SH2SXY, LUT_SXY2SH, MAX_LEVELS,
+};
+use core::iter::FusedIterator;
+
+/// Position on the hilbert curve which remembers the state of every level.
+///
+/// Moving the position only needs to decode the levels whose digits changed, since the states of
+/// all levels above stay the same.
+#[derive(Clone, Debug)]
+pub(crate) struct Walker<T: Unsigned> {
+    pub(crate) h: T::Key,
+    pub(crate) x: T,
+    pub(crate) y: T,
+    order: u8,
+    // State in which the digit of each level is processed, level 0 holds the lowest digit
+    states: [u8; MAX_LEVELS],
+}
+
+impl<T: UnsignedCurve> Walker<T> {
+    pub(crate) fn new(h: T::Key, order: u8) -> Self {
+        let mut walker = Walker {
+            h,
+            x: T::ZERO,
+            y: T::ZERO,
+            order,
+            states: [0; MAX_LEVELS],
+        };
+        walker.seek(h);
+        walker
+    }
+
+    /// Jump to an arbitrary index. The coordinates are decoded with [`h2xy`].
+    pub(crate) fn seek(&mut self, h: T::Key) {
+        self.h = h;
+        (self.x, self.y) = h2xy(h, self.order);
+        let mut state = 0;
+        for level in (0..usize::from(self.order)).rev() {
+            self.states[level] = state;
+            state = LUT_SH2SXY[usize::from(state << 2 | self.digit(level))] >> 2;
+        }
+    }
+
+    #[inline]
+    fn digit(&self, level: usize) -> u8 {
+        ((self.h >> (level << 1)) & 3.into()).as_u8()
+    }
+
+    /// Decode the lowest `levels` digits of `h` again. All digits above must be unchanged.
+    fn redecode(&mut self, levels: usize) {
+        let mut state = self.states[levels - 1];
+        for level in (0..levels).rev() {
+            self.states[level] = state;
+            let r = LUT_SH2SXY[usize::from(state << 2 | self.digit(level))];
+            let bit = T::from(1) << level;
+            self.x = if r & 0b10 == 0 {
+                self.x & !bit
+            } else {
+                self.x | bit
+            };
+            self.y = if r & 0b01 == 0 {
+                self.y & !bit
+            } else {
+                self.y | bit
+            };
+            state = r >> 2;
+        }
+    }
+
+    /// Encode the lowest `levels` bits of `x` and `y` again. All bits above must be unchanged.
+    fn reencode(&mut self, levels: usize) {
+        let mut state = self.states[levels - 1];
+        let mut h = self.h & !low_bits::<T::Key>((levels << 1) as u32);
+        for level in (0..levels).rev() {
+            self.states[level] = state;
+            let xy = ((self.x >> level).as_u8() & 1) << 1 | (self.y >> level).as_u8() & 1;
+            let sh = LUT_SXY2SH[usize::from(state << 2 | xy)];
+            h |= T::Key::from(sh & 0b11) << (level << 1);
+            state = sh >> 2;
+        }
+        self.h = h;
+    }
+
+    /// Move to the index `h` within the curve. Only the digits up to the highest changed one are decoded again.
+    pub(crate) fn jump(&mut self, h: T::Key) {
+        let changed = self.h ^ h;
+        if changed != T::Key::ZERO {
+            let key_bits = size_of::<T::Key>() << 3;
+            self.h = h;
+            self.redecode((key_bits - changed.leading_zeros() as usize + 1) >> 1);
+        }
+    }
+
+    /// Move to the cell `(x, y)` within the curve. Only the levels up to the highest changed bit are encoded
+    /// again.
+    pub(crate) fn move_to(&mut self, x: T, y: T) {
+        let changed = (self.x ^ x) | (self.y ^ y);
+        if changed != T::ZERO {
+            let coord_bits = size_of::<T>() << 3;
+            (self.x, self.y) = (x, y);
+            self.reencode(coord_bits - changed.leading_zeros() as usize);
+        }
+    }
+
+    /// The hilbert curve order
+    pub(crate) fn order(&self) -> u8 {
+        self.order
+    }
+
+    /// Move to `h + 1`. Must not be called on the last index of the curve.
+    #[inline]
+    pub(crate) fn step_forward(&mut self) {
+        // Trailing `3` digits wrap around to `0` and the digit above is incremented
+        let levels = (!self.h).trailing_zeros() as usize / 2 + 1;
+        self.h = self.h + 1.into();
+        self.redecode(levels);
+    }
+
+    /// Move to `h - 1`. Must not be called on the first index of the curve.
+    #[inline]
+    pub(crate) fn step_backward(&mut self) {
+        // Trailing `0` digits wrap around to `3` and the digit above is decremented
+        let levels = self.h.trailing_zeros() as usize / 2 + 1;
+        self.h = self.h - 1.into();
+        self.redecode(levels);
+    }
+}
+
+/// Iterate lazily over the `(x, y)` coordinates of the hilbert indices `start..end`.
+///
+/// The first point is decoded with [`h2xy`], all following points are computed incrementally.
+/// The range is empty if `start >= end`.
+///
+/// # Panics
+/// If `order` exceeds the number of bits of `T` or `end` exceeds the number of points of the curve.
+///
+/// # Examples
+///```
+/// let points: Vec<(u32, u32)> = fast_hilbert::h2xy_range(1, 3, 1).collect();
+/// assert_eq!(points, [(0, 1), (1, 1)]);
+///```
+pub fn h2xy_range<T: UnsignedCurve>(start: T::Key, end: T::Key, order: u8) -> HilbertIter<T> {
+    assert_order::<T>(order);
+    assert!(
+        order == max_order::<T>() || end <= T::Key::from(1) << (usize::from(order) << 1),
+        "end {end:?} exceeds the hilbert curve of order {order}"
+    );
+    if start >= end {
+        let mut iter = HilbertIter::inclusive(T::Key::ZERO, T::Key::ZERO, order);
+        iter.done = true;
+        iter
+    } else {
+        HilbertIter::inclusive(start, end - 1.into(), order)
+    }
+}
+
+/// Iterator over the `(x, y)` coordinates of a hilbert curve in curve order.
+///
+/// Every step only decodes the digits of the hilbert index which changed, which costs amortized `O(1)`
+/// instead of the `O(order)` of a full [`h2xy`] call. [`Iterator::nth`] and [`DoubleEndedIterator::nth_back`]
+/// seek to the new position with [`h2xy`].
+///
+/// The number of points of a `u32` or `u64` curve with a high order can exceed `usize::MAX`.
+/// In that case [`Iterator::size_hint`] has no upper bound. [`ExactSizeIterator`] is only implemented for the
+/// coordinate types whose full curve always fits into `usize`, which are `u8` and, on 64-bit targets, `u16`.
+///
+/// # Examples
+///```
+/// use fast_hilbert::HilbertIter;
+///
+/// let points: Vec<(u8, u8)> = HilbertIter::new(1).collect();
+/// assert_eq!(points, [(0, 0), (0, 1), (1, 1), (1, 0)]);
+///
+/// let last = HilbertIter::<u32>::new(16).next_back();
+/// assert_eq!(last, Some((65535, 0)));
+///```
+#[derive(Clone, Debug)]
+pub struct HilbertIter<T: Unsigned> {
+    front: Walker<T>,
+    back: Walker<T>,
+    done: bool,
+}
+
+impl<T: UnsignedCurve> HilbertIter<T> {
+    /// Iterate over all `4^order` points of the hilbert curve with the given order.
+    ///
+    /// # Panics
+    /// If `order` exceeds the number of bits of `T`.
+    #[must_use]
+    pub fn new(order: u8) -> Self {
+        assert_order::<T>(order);
+        Self::inclusive(T::Key::ZERO, low_bits(u32::from(order) << 1), order)
+    }
+
+    /// Iterate over the indices `first..=last`, which must be a non empty range within the order
+    fn inclusive(first: T::Key, last: T::Key, order: u8) -> Self {
+        HilbertIter {
+            front: Walker::new(first, order),
+            back: Walker::new(last, order),
+            done: false,
+        }
+    }
+
+    // Number of remaining points minus one, since that always fits into the key
+    fn remaining(&self) -> T::Key {
+        self.back.h - self.front.h
+    }
+}
+
+impl<T: UnsignedCurve> Iterator for HilbertIter<T> {
+    type Item = (T, T);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let item = (self.front.x, self.front.y);
+        if self.front.h == self.back.h {
+            self.done = true;
+        } else {
+            self.front.step_forward();
+        }
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+        match self
+            .remaining()
+            .try_into()
+            .ok()
+            .and_then(|n: usize| n.checked_add(1))
+        {
+            Some(n) => (n, Some(n)),
+            None => (usize::MAX, None),
+        }
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match T::Key::try_from(n) {
+            Ok(n) if n <= self.remaining() => {
+                if n != T::Key::ZERO {
+                    self.front.seek(self.front.h + n);
+                }
+                self.next()
+            }
+            _ => {
+                self.done = true;
+                None
+            }
+        }
+    }
+}
+
+impl<T: UnsignedCurve> DoubleEndedIterator for HilbertIter<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let item = (self.back.x, self.back.y);
+        if self.front.h == self.back.h {
+            self.done = true;
+        } else {
+            self.back.step_backward();
+        }
+        Some(item)
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match T::Key::try_from(n) {
+            Ok(n) if n <= self.remaining() => {
+                if n != T::Key::ZERO {
+                    self.back.seek(self.back.h - n);
+                }
+                self.next_back()
+            }
+            _ => {
+                self.done = true;
+                None
+            }
+        }
+    }
+}
+
+// The full curve has `4^order` points, one more than the largest index
+#[cfg(any(target_pointer_width = "32", target_pointer_width = "64"))]
+impl ExactSizeIterator for HilbertIter<u8> {}
+
+#[cfg(target_pointer_width = "64")]
+impl ExactSizeIterator for HilbertIter<u16> {}
+
+impl<T: UnsignedCurve> FusedIterator for HilbertIter<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn iter_matches_h2xy() {
+        for order in 1..=10u8 {
+            let mut count = 0u32;
+            for (h, xy) in HilbertIter::<u16>::new(order).enumerate() {
+                assert_eq!(xy, h2xy::<u16>(h as u32, order), "order {order} h {h}");
+                count += 1;
+            }
+            assert_eq!(count, 1 << (2 * order));
+        }
+    }
+
+    #[test]
+    fn iter_rev_matches_h2xy() {
+        for order in 1..=8u8 {
+            let max = 1u64 << (2 * order);
+            for (h, xy) in (0..max).rev().zip(HilbertIter::<u32>::new(order).rev()) {
+                assert_eq!(xy, h2xy::<u32>(h, order), "order {order} h {h}");
+            }
+        }
+    }
+
+    #[test]
+    fn iter_full_order() {
+        let points: Vec<(u8, u8)> = HilbertIter::new(8).collect();
+        assert_eq!(points.len(), 1 << 16);
+        for (h, &xy) in points.iter().enumerate() {
+            assert_eq!(xy, h2xy::<u8>(h as u16, 8));
+        }
+    }
+
+    #[test]
+    fn iter_order_zero() {
+        let points: Vec<(u32, u32)> = HilbertIter::new(0).collect();
+        assert_eq!(points, [(0, 0)]);
+    }
+
+    #[test]
+    fn iter_both_ends() {
+        let mut iter = HilbertIter::<u16>::new(3);
+        let mut points = Vec::new();
+        assert_eq!(iter.len(), 64);
+        while let Some(front) = iter.next() {
+            points.push((front, iter.next_back()));
+        }
+        assert_eq!(points.len(), 32);
+        for (h, (front, back)) in points.into_iter().enumerate() {
+            assert_eq!(front, h2xy(h as u32, 3));
+            assert_eq!(back, Some(h2xy(63 - h as u32, 3)));
+        }
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn iter_nth() {
+        let mut iter = HilbertIter::<u16>::new(5);
+        assert_eq!(iter.next(), Some(h2xy(0, 5)));
+        assert_eq!(iter.nth(100), Some(h2xy(101, 5)));
+        assert_eq!(iter.next(), Some(h2xy(102, 5)));
+        assert_eq!(iter.nth_back(20), Some(h2xy(1003, 5)));
+        assert_eq!(iter.next_back(), Some(h2xy(1002, 5)));
+        assert_eq!(iter.len(), 1002 - 103);
+        assert_eq!(iter.nth(2000), None);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn range_matches_h2xy() {
+        fn check<T: UnsignedCurve>(start: T::Key, len: u8, order: u8) {
+            let mut h = start;
+            let mut count = 0;
+            for xy in h2xy_range::<T>(start, start + len.into(), order) {
+                assert_eq!(xy, h2xy::<T>(h, order), "h {h:?} order {order}");
+                h = h + 1.into();
+                count += 1;
+            }
+            assert_eq!(count, len);
+        }
+        for start in 0..200 {
+            check::<u8>(start, 56, 4);
+            check::<u16>(start.into(), 100, 7);
+        }
+        check::<u8>(u16::MAX - 255, 255, 8);
+        check::<u32>(123_456_789, 200, 20);
+        check::<u32>(u64::MAX - 200, 200, 32);
+        check::<u64>(u128::MAX - 200, 200, 64);
+        check::<u64>(1 << 100, 200, 60);
+    }
+
+    #[test]
+    fn range_rev() {
+        let forward: Vec<(u64, u64)> = h2xy_range(1 << 70, (1 << 70) + 500, 40).collect();
+        let mut backward: Vec<(u64, u64)> =
+            h2xy_range(1 << 70, (1 << 70) + 500, 40).rev().collect();
+        backward.reverse();
+        assert_eq!(forward, backward);
+    }
+
+    #[test]
+    fn range_empty() {
+        assert_eq!(h2xy_range::<u32>(5, 5, 3).next(), None);
+        assert_eq!(h2xy_range::<u8>(6, 5, 3).len(), 0);
+        assert_eq!(h2xy_range::<u8>(0, 64, 3).len(), 64);
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds the hilbert curve")]
+    fn range_exceeds_order() {
+        let _ = h2xy_range::<u32>(0, 65, 3);
+    }
+
+    #[test]
+    fn iter_huge_curve() {
+        let mut iter = HilbertIter::<u64>::new(64);
+        assert_eq!(iter.size_hint(), (usize::MAX, None));
+        #[cfg(target_pointer_width = "64")]
+        assert_eq!(HilbertIter::<u16>::new(16).len(), 1 << 32);
+        assert_eq!(iter.next(), Some((0, 0)));
+        assert_eq!(iter.next_back(), Some((u64::MAX, 0)));
+        let h = u128::MAX - 3;
+        assert_eq!(iter.nth_back(2), Some(h2xy(h, 64)));
+        assert_eq!(iter.next_back(), Some(h2xy(h - 1, 64)));
+    }
+}
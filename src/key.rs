@@ -0,0 +1,312 @@
+use crate::{assert_order, h_to_tile, low_bits, max_order, tile_to_h, Unsigned, UnsignedBase};
+use core::fmt;
+use core::str::FromStr;
+
+/// Error when parsing a [`HilbertKey`] or [`Quadkey`] from a string
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseKeyError {
+    /// The character at `position` is not a base-4 digit
+    InvalidDigit { position: usize, digit: char },
+    /// The string has more digits than the type can hold
+    TooLong { length: usize, max_length: u8 },
+}
+
+impl fmt::Display for ParseKeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseKeyError::InvalidDigit { position, digit } => {
+                write!(
+                    f,
+                    "invalid digit {digit:?} at position {position}, expected 0 to 3"
+                )
+            }
+            ParseKeyError::TooLong { length, max_length } => write!(
+                f,
+                "key with {length} digits exceeds the maximum of {max_length} digits"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for ParseKeyError {}
+
+/// Parse a string of base-4 digits, calling `push` for every digit from the most significant one.
+/// Returns the number of digits.
+fn parse_digits<T: Unsigned>(s: &str, mut push: impl FnMut(u8)) -> Result<u8, ParseKeyError> {
+    let max_length = max_order::<T>();
+    let length = s.chars().count();
+    if length > usize::from(max_length) {
+        return Err(ParseKeyError::TooLong { length, max_length });
+    }
+    for (position, digit) in s.chars().enumerate() {
+        match digit.to_digit(4) {
+            Some(value) => push(value as u8),
+            None => return Err(ParseKeyError::InvalidDigit { position, digit }),
+        }
+    }
+    Ok(length as u8)
+}
+
+/// Hilbert index of a curve with `order` in a human readable form.
+///
+/// It is formatted as `order` base-4 digits, which are the quadrants from the coarsest to the finest level.
+///
+/// # Examples
+///```
+/// use fast_hilbert::HilbertKey;
+///
+/// let key = HilbertKey::<u32>::new(0b10_01_11, 4);
+/// assert_eq!(key.to_string(), "0213");
+/// assert_eq!("0213".parse(), Ok(key));
+///```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HilbertKey<T: Unsigned> {
+    h: T::Key,
+    order: u8,
+}
+
+impl<T: Unsigned> HilbertKey<T> {
+    /// Key of the hilbert index `h` of a curve with `order`.
+    ///
+    /// # Panics
+    /// If `order` exceeds the number of bits of `T` or `h` exceeds the indices of `order`.
+    pub fn new(h: T::Key, order: u8) -> Self {
+        assert_order::<T>(order);
+        assert!(
+            h <= low_bits(u32::from(order) << 1),
+            "index {h:?} exceeds the indices of order {order}"
+        );
+        Self { h, order }
+    }
+
+    /// Hilbert index
+    #[must_use]
+    pub fn h(self) -> T::Key {
+        self.h
+    }
+
+    /// The hilbert curve order, which is the number of digits
+    #[must_use]
+    pub fn order(self) -> u8 {
+        self.order
+    }
+}
+
+impl<T: Unsigned> fmt::Display for HilbertKey<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for level in (0..usize::from(self.order)).rev() {
+            let digit = (self.h >> (level << 1)).as_u8() & 3;
+            write!(f, "{digit}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Unsigned> FromStr for HilbertKey<T> {
+    type Err = ParseKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut h = T::Key::ZERO;
+        let order = parse_digits::<T>(s, |digit| h = h << 2usize | T::Key::from(digit))?;
+        Ok(Self { h, order })
+    }
+}
+
+/// Tile address in the quadkey format of Bing Maps.
+///
+/// Each digit holds one bit of the tile column `x` (value 1) and of the tile row `y` (value 2), starting with
+/// the coarsest level. The number of digits is the zoom level `z`. Tiles use the same coordinates as
+/// [`tile_to_h`], so the quadkey can be converted to and from its hilbert index.
+///
+/// # Examples
+///```
+/// use fast_hilbert::Quadkey;
+///
+/// let quadkey: Quadkey<u32> = "213".parse().unwrap();
+/// assert_eq!(quadkey, Quadkey::new(3, 3, 5));
+/// let h = quadkey.to_h();
+/// assert_eq!(h, fast_hilbert::tile_to_h(3, 3u32, 5));
+/// assert_eq!(Quadkey::<u32>::from_h(h, 3).to_string(), "213");
+///```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Quadkey<T: Unsigned> {
+    z: u8,
+    x: T,
+    y: T,
+}
+
+impl<T: Unsigned> Quadkey<T> {
+    /// Quadkey of the tile `(x, y)` at zoom level `z`.
+    ///
+    /// # Panics
+    /// If `z` exceeds the number of bits of `T` or the tile is outside of the zoom level.
+    pub fn new(z: u8, x: T, y: T) -> Self {
+        assert_order::<T>(z);
+        let max: T = low_bits(z.into());
+        assert!(
+            x <= max && y <= max,
+            "tile ({x:?}, {y:?}) is outside of zoom level {z}"
+        );
+        Self { z, x, y }
+    }
+
+    /// Quadkey of the tile with the hilbert index `h` at zoom level `z`.
+    ///
+    /// # Panics
+    /// If `z` exceeds the number of bits of `T` or `h` exceeds the indices of the zoom level.
+    pub fn from_h(h: T::Key, z: u8) -> Self {
+        let (x, y) = h_to_tile(h, z);
+        Self { z, x, y }
+    }
+
+    /// Zoom level, which is the number of digits
+    #[must_use]
+    pub fn z(self) -> u8 {
+        self.z
+    }
+
+    /// Tile column
+    #[must_use]
+    pub fn x(self) -> T {
+        self.x
+    }
+
+    /// Tile row
+    #[must_use]
+    pub fn y(self) -> T {
+        self.y
+    }
+
+    /// Hilbert index of the tile
+    pub fn to_h(self) -> T::Key {
+        tile_to_h(self.z, self.x, self.y)
+    }
+}
+
+impl<T: Unsigned> fmt::Display for Quadkey<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for level in (0..usize::from(self.z)).rev() {
+            let x = (self.x >> level).as_u8() & 1;
+            let y = (self.y >> level).as_u8() & 1;
+            write!(f, "{}", x | y << 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Unsigned> FromStr for Quadkey<T> {
+    type Err = ParseKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (mut x, mut y) = (T::ZERO, T::ZERO);
+        let z = parse_digits::<T>(s, |digit| {
+            x = x << 1usize | T::from(digit & 1);
+            y = y << 1usize | T::from(digit >> 1);
+        })?;
+        Ok(Self { z, x, y })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hilbert_key_round_trip() {
+        for order in 0..=5u8 {
+            for h in 0..1u16 << (2 * order) {
+                let key = HilbertKey::<u8>::new(h, order);
+                let text = key.to_string();
+                assert_eq!(text.len(), usize::from(order));
+                assert_eq!(text.parse(), Ok(key));
+            }
+        }
+        let key = HilbertKey::<u64>::new(u128::MAX, 64);
+        assert_eq!(key.to_string(), "3".repeat(64));
+        assert_eq!(key.to_string().parse(), Ok(key));
+        assert_eq!("".parse(), Ok(HilbertKey::<u32>::new(0, 0)));
+    }
+
+    #[test]
+    fn quadkey_round_trip() {
+        for z in 0..=5u8 {
+            for x in 0..1u16 << z {
+                for y in 0..1u16 << z {
+                    let quadkey = Quadkey::new(z, x, y);
+                    let text = quadkey.to_string();
+                    assert_eq!(text.parse(), Ok(quadkey));
+                    assert_eq!(Quadkey::from_h(quadkey.to_h(), z), quadkey);
+                }
+            }
+        }
+        // Examples from the Bing Maps tile system documentation
+        assert_eq!(Quadkey::<u32>::new(3, 3, 5).to_string(), "213");
+        assert_eq!("".parse(), Ok(Quadkey::<u8>::new(0, 0, 0)));
+        let max = Quadkey::<u32>::new(32, u32::MAX, 0);
+        assert_eq!(max.to_string().parse(), Ok(max));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            "0124".parse::<HilbertKey<u32>>(),
+            Err(ParseKeyError::InvalidDigit {
+                position: 3,
+                digit: '4'
+            })
+        );
+        assert_eq!(
+            "1ä".parse::<Quadkey<u32>>(),
+            Err(ParseKeyError::InvalidDigit {
+                position: 1,
+                digit: 'ä'
+            })
+        );
+        assert_eq!(
+            "0".repeat(9).parse::<Quadkey<u8>>(),
+            Err(ParseKeyError::TooLong {
+                length: 9,
+                max_length: 8
+            })
+        );
+        assert_eq!(
+            "0".repeat(33).parse::<HilbertKey<u32>>(),
+            Err(ParseKeyError::TooLong {
+                length: 33,
+                max_length: 32
+            })
+        );
+        assert_eq!(
+            ParseKeyError::InvalidDigit {
+                position: 3,
+                digit: '4'
+            }
+            .to_string(),
+            "invalid digit '4' at position 3, expected 0 to 3"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds the number of bits")]
+    fn hilbert_key_beyond_type() {
+        let _ = HilbertKey::<u8>::new(0, 20);
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds the indices of order")]
+    fn hilbert_key_index_beyond_order() {
+        let _ = HilbertKey::<u32>::new(16, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds the number of bits")]
+    fn quadkey_beyond_type() {
+        let _ = Quadkey::<u8>::new(9, 0, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "outside of zoom level")]
+    fn quadkey_tile_outside_of_zoom() {
+        let _ = Quadkey::<u32>::new(2, 4, 0);
+    }
+}
@@ -37,7 +37,9 @@
 //! **512 Bytes of RAM** for the lookup tables (one for 2D->1D and another for 1D->2D).
 //!
 
-#![cfg_attr(not(test), no_std)]
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
+// Only the opt-in `simd` feature needs `unsafe` to call into `core::arch`
+#![cfg_attr(not(feature = "simd"), forbid(unsafe_code))]
 
 use core::convert::{From, TryInto};
 use core::fmt::Debug;
@@ -46,7 +48,54 @@ use core::ops::{BitAnd, BitOr, BitOrAssign, Not, Shl, ShlAssign, Shr, ShrAssign,
 #[cfg(feature = "checked")]
 mod checked;
 #[cfg(feature = "checked")]
-pub use checked::{h2xy_checked, max_coord, max_index, max_order, xy2h_checked, OrderError};
+pub use checked::{h2xy_checked, max_coord, max_index, xy2h_checked, OrderError};
+mod signed;
+pub use signed::{h2xy_signed, xy2h_signed, Signed};
+mod slice;
+pub use slice::{h2xy_slice, h2xy_slice_soa, xy2h_slice, xy2h_slice_soa};
+mod iter;
+pub use iter::{h2xy_range, HilbertIter};
+mod cell;
+pub use cell::HilbertCell;
+mod cursor;
+pub use cursor::HilbertCursor;
+mod direction;
+pub use direction::{curve_direction, neighbor_keys, Dir};
+mod generalized;
+pub use generalized::GeneralizedHilbert;
+#[cfg(feature = "geo")]
+pub mod geo;
+mod hilbert3d;
+pub use hilbert3d::{h2xyz, xyz2h, Unsigned3D};
+mod hilbert_nd;
+pub use hilbert_nd::{decode, encode};
+mod morton;
+pub use morton::{morton2xy, xy2morton};
+mod key;
+pub use key::{HilbertKey, ParseKeyError, Quadkey};
+mod moore;
+pub use moore::{moore2xy, xy2moore};
+mod orientation;
+pub use orientation::{
+    h2xy_oriented, h2xy_unbounded, xy2h_oriented, xy2h_unbounded, Axis, Orientation,
+};
+mod peano;
+pub use peano::{peano2xy, xy2peano};
+mod quantize;
+pub use quantize::{HilbertQuantizer, QuantizeError};
+mod rescale;
+pub use rescale::rescale_h;
+mod rect;
+pub use rect::{
+    next_in_box, prev_in_box, range_bbox, range_bbox_inclusive, rect_to_ranges,
+    rect_to_ranges_bounded, RectRanges,
+};
+mod tile;
+pub use tile::{children, h_to_tile, parent, tile_to_h};
+#[cfg(feature = "simd")]
+mod simd;
+#[cfg(feature = "simd")]
+pub use simd::xy2h_bulk;
 pub trait UnsignedBase:
     From<u8>
     + Copy
@@ -93,6 +142,18 @@ macro_rules! base_impl {
                 self as u8
             }
         }
+
+        impl ops::UnsignedOps for $T {
+            #[inline]
+            fn trailing_zeros(self) -> u32 {
+                <$T>::trailing_zeros(self)
+            }
+
+            #[inline]
+            fn count_ones(self) -> u32 {
+                <$T>::count_ones(self)
+            }
+        }
     };
 }
 
@@ -133,9 +194,105 @@ impl Unsigned for u8 {
     const SIXTY_THREE: Self::Key = 63;
 }
 
+// Operations beyond `UnsignedBase` which are only implemented for the primitive integers. The traits are public
+// within a private module, so they can bound public functions without becoming part of `UnsignedBase`.
+mod ops {
+    use super::{Unsigned, UnsignedBase};
+    use core::convert::TryFrom;
+    use core::ops::{Add, BitXor, Div, Mul, Rem};
+
+    /// Arithmetic of the primitive unsigned integers
+    pub trait UnsignedOps:
+        UnsignedBase
+        + TryFrom<usize>
+        + BitXor<Output = Self>
+        + Add<Self, Output = Self>
+        + Mul<Self, Output = Self>
+        + Div<Self, Output = Self>
+        + Rem<Self, Output = Self>
+    {
+        fn trailing_zeros(self) -> u32;
+        fn count_ones(self) -> u32;
+    }
+
+    /// Coordinate type whose coordinates and keys are primitive unsigned integers
+    pub trait UnsignedCurve: Unsigned<Key: UnsignedOps> + UnsignedOps {}
+
+    impl<T> UnsignedCurve for T where T: Unsigned<Key: UnsignedOps> + UnsignedOps {}
+}
+use ops::{UnsignedCurve, UnsignedOps};
+
+// Single level of the state machine from 1D to 2D
+// SSHH => SSXY
+// 4 bits => 4 bits
+const LUT_SH2SXY: [u8; 16] = [
+    0b0100, 0b0001, 0b0011, 0b1010, //
+    0b0000, 0b0110, 0b0111, 0b1101, //
+    0b1111, 0b1001, 0b1000, 0b0010, //
+    0b1011, 0b1110, 0b1100, 0b0101,
+];
+
+// Single level of the state machine from 2D to 1D, the inverse of `LUT_SH2SXY`
+// SSXY => SSHH
+// 4 bits => 4 bits
+const LUT_SXY2SH: [u8; 16] = invert_lut(LUT_SH2SXY);
+
+const fn invert_lut(lut: [u8; 16]) -> [u8; 16] {
+    let mut inverse = [0; 16];
+    let mut sh = 0;
+    while sh < 16 {
+        let sxy = lut[sh];
+        // The state of the input is kept in the index, the state of the output in the value
+        inverse[(sh & 0b1100) | (sxy as usize & 0b11)] = (sxy & 0b1100) | (sh as u8 & 0b11);
+        sh += 1;
+    }
+    inverse
+}
+
+// Mapping from State and coordinates to hilbert states
+// SXXXYYY => SHHH
+//   8 bit => 8 bit
+const LUT_3: [u8; 256] = [
+    64, 1, 206, 79, 16, 211, 84, 21, 131, 2, 205, 140, 81, 82, 151, 22, 4, 199, 8, 203, 158, 157,
+    88, 25, 69, 70, 73, 74, 31, 220, 155, 26, 186, 185, 182, 181, 32, 227, 100, 37, 59, 248, 55,
+    244, 97, 98, 167, 38, 124, 61, 242, 115, 174, 173, 104, 41, 191, 62, 241, 176, 47, 236, 171,
+    42, 0, 195, 68, 5, 250, 123, 60, 255, 65, 66, 135, 6, 249, 184, 125, 126, 142, 141, 72, 9, 246,
+    119, 178, 177, 15, 204, 139, 10, 245, 180, 51, 240, 80, 17, 222, 95, 96, 33, 238, 111, 147, 18,
+    221, 156, 163, 34, 237, 172, 20, 215, 24, 219, 36, 231, 40, 235, 85, 86, 89, 90, 101, 102, 105,
+    106, 170, 169, 166, 165, 154, 153, 150, 149, 43, 232, 39, 228, 27, 216, 23, 212, 108, 45, 226,
+    99, 92, 29, 210, 83, 175, 46, 225, 160, 159, 30, 209, 144, 48, 243, 116, 53, 202, 75, 12, 207,
+    113, 114, 183, 54, 201, 136, 77, 78, 190, 189, 120, 57, 198, 71, 130, 129, 63, 252, 187, 58,
+    197, 132, 3, 192, 234, 107, 44, 239, 112, 49, 254, 127, 233, 168, 109, 110, 179, 50, 253, 188,
+    230, 103, 162, 161, 52, 247, 56, 251, 229, 164, 35, 224, 117, 118, 121, 122, 218, 91, 28, 223,
+    138, 137, 134, 133, 217, 152, 93, 94, 11, 200, 7, 196, 214, 87, 146, 145, 76, 13, 194, 67, 213,
+    148, 19, 208, 143, 14, 193, 128,
+];
+
+// Mapping from hilbert states to 2D coordinates
+// SHHH => SXXXYYY
+//   8 bit => 8 bit
+const LUT_3_REV: [u8; 256] = [
+    64, 1, 9, 136, 16, 88, 89, 209, 18, 90, 91, 211, 139, 202, 194, 67, 4, 76, 77, 197, 70, 7, 15,
+    142, 86, 23, 31, 158, 221, 149, 148, 28, 36, 108, 109, 229, 102, 39, 47, 174, 118, 55, 63, 190,
+    253, 181, 180, 60, 187, 250, 242, 115, 235, 163, 162, 42, 233, 161, 160, 40, 112, 49, 57, 184,
+    0, 72, 73, 193, 66, 3, 11, 138, 82, 19, 27, 154, 217, 145, 144, 24, 96, 33, 41, 168, 48, 120,
+    121, 241, 50, 122, 123, 243, 171, 234, 226, 99, 100, 37, 45, 172, 52, 124, 125, 245, 54, 126,
+    127, 247, 175, 238, 230, 103, 223, 151, 150, 30, 157, 220, 212, 85, 141, 204, 196, 69, 6, 78,
+    79, 199, 255, 183, 182, 62, 189, 252, 244, 117, 173, 236, 228, 101, 38, 110, 111, 231, 159,
+    222, 214, 87, 207, 135, 134, 14, 205, 133, 132, 12, 84, 21, 29, 156, 155, 218, 210, 83, 203,
+    131, 130, 10, 201, 129, 128, 8, 80, 17, 25, 152, 32, 104, 105, 225, 98, 35, 43, 170, 114, 51,
+    59, 186, 249, 177, 176, 56, 191, 254, 246, 119, 239, 167, 166, 46, 237, 165, 164, 44, 116, 53,
+    61, 188, 251, 179, 178, 58, 185, 248, 240, 113, 169, 232, 224, 97, 34, 106, 107, 227, 219, 147,
+    146, 26, 153, 216, 208, 81, 137, 200, 192, 65, 2, 74, 75, 195, 68, 5, 13, 140, 20, 92, 93, 213,
+    22, 94, 95, 215, 143, 206, 198, 71,
+];
+
 /// Convert form 2D to 1D hilbert space.
 /// Input type `T` must have half the capacity of the result type. For example (u32, u32) => u64.
 ///
+/// Coordinates beyond `order` are not rejected. Only the parity of `order` influences the result, because it selects
+/// the axis of the first step. See [`xy2h_unbounded`] for the same curve without an order.
+///
 /// # Arguments
 /// * `x` - Coordinate in 2D space
 /// * `y` - Coordinate in 2D space
@@ -147,29 +304,59 @@ impl Unsigned for u8 {
 /// assert_eq!(hilbert, 0b11u128);
 ///```
 pub fn xy2h<T: Unsigned>(x: T, y: T, order: u8) -> <T as Unsigned>::Key {
-    // Mapping from State and coordinates to hilbert states
-    // SXXXYYY => SHHH
-    //   8 bit => 8 bit
-    const LUT_3: [u8; 256] = [
-        64, 1, 206, 79, 16, 211, 84, 21, 131, 2, 205, 140, 81, 82, 151, 22, 4, 199, 8, 203, 158,
-        157, 88, 25, 69, 70, 73, 74, 31, 220, 155, 26, 186, 185, 182, 181, 32, 227, 100, 37, 59,
-        248, 55, 244, 97, 98, 167, 38, 124, 61, 242, 115, 174, 173, 104, 41, 191, 62, 241, 176, 47,
-        236, 171, 42, 0, 195, 68, 5, 250, 123, 60, 255, 65, 66, 135, 6, 249, 184, 125, 126, 142,
-        141, 72, 9, 246, 119, 178, 177, 15, 204, 139, 10, 245, 180, 51, 240, 80, 17, 222, 95, 96,
-        33, 238, 111, 147, 18, 221, 156, 163, 34, 237, 172, 20, 215, 24, 219, 36, 231, 40, 235, 85,
-        86, 89, 90, 101, 102, 105, 106, 170, 169, 166, 165, 154, 153, 150, 149, 43, 232, 39, 228,
-        27, 216, 23, 212, 108, 45, 226, 99, 92, 29, 210, 83, 175, 46, 225, 160, 159, 30, 209, 144,
-        48, 243, 116, 53, 202, 75, 12, 207, 113, 114, 183, 54, 201, 136, 77, 78, 190, 189, 120, 57,
-        198, 71, 130, 129, 63, 252, 187, 58, 197, 132, 3, 192, 234, 107, 44, 239, 112, 49, 254,
-        127, 233, 168, 109, 110, 179, 50, 253, 188, 230, 103, 162, 161, 52, 247, 56, 251, 229, 164,
-        35, 224, 117, 118, 121, 122, 218, 91, 28, 223, 138, 137, 134, 133, 217, 152, 93, 94, 11,
-        200, 7, 196, 214, 87, 146, 145, 76, 13, 194, 67, 213, 148, 19, 208, 143, 14, 193, 128,
-    ];
+    xy2h_lowest(x, y, xy_lowest_order(x | y, order))
+}
 
+/// Lowest order which is sufficient to encode all coordinates whose bits are contained in `xy`.
+#[inline]
+fn xy_lowest_order<T: Unsigned>(xy: T, order: u8) -> u8 {
     let coor_bits = (size_of::<T>() << 3) as u32;
-    let useless_bits = (x | y).leading_zeros() & !1;
-    let lowest_order = (coor_bits - useless_bits) as u8 + (order & 1);
+    let useless_bits = xy.leading_zeros() & !1;
+    (coor_bits - useless_bits) as u8 + (order & 1)
+}
+
+/// Lowest order which is sufficient to decode all hilbert indices whose bits are contained in `h`.
+#[inline]
+fn h_lowest_order<T: Unsigned>(h: T::Key, order: u8) -> u8 {
+    let coor_bits = (size_of::<T>() << 3) as u8;
+    let useless_bits = (h.leading_zeros() >> 1) as u8 & !1;
+    coor_bits - useless_bits + (order & 1)
+}
+
+/// The maximum allowed order for a given type. This is the number of bits in the type.
+#[inline]
+#[must_use]
+pub const fn max_order<T: UnsignedBase>() -> u8 {
+    (size_of::<T>() << 3) as u8
+}
 
+/// Panic if `order` exceeds the number of bits of the coordinate type `T`.
+#[inline]
+#[track_caller]
+fn assert_order<T: UnsignedBase>(order: u8) {
+    assert!(
+        order <= max_order::<T>(),
+        "order {order} exceeds the number of bits of the coordinate type"
+    );
+}
+
+// Highest order of all coordinate types, which bounds the number of levels kept by iterators and cursors
+const MAX_LEVELS: usize = max_order::<u64>() as usize;
+
+/// Value with the lowest `bits` bits set.
+#[inline]
+fn low_bits<U: UnsignedBase>(bits: u32) -> U {
+    let type_bits = (size_of::<U>() << 3) as u32;
+    if bits == 0 {
+        U::ZERO
+    } else {
+        !U::ZERO >> (type_bits - bits) as usize
+    }
+}
+
+/// Run the `LUT_3` state machine for an already computed `lowest_order`.
+#[inline]
+fn xy2h_lowest<T: Unsigned>(x: T, y: T, lowest_order: u8) -> <T as Unsigned>::Key {
     let mut result: T::Key = T::Key::ZERO;
     let mut state = 0u8;
     let mut shift_factor = lowest_order as i8 - 3;
@@ -208,6 +395,8 @@ pub fn xy2h<T: Unsigned>(x: T, y: T, order: u8) -> <T as Unsigned>::Key {
 ///
 /// Input type `T` must have double the capacity of the result types. For example u64 => (u32, u32).
 ///
+/// Like [`xy2h`], only the parity of `order` influences the result. See [`h2xy_unbounded`].
+///
 /// # Arguments
 /// * `h`     - Coordinate in 1D hilbert space
 /// * `order` - Hilbert curve order
@@ -219,28 +408,12 @@ pub fn xy2h<T: Unsigned>(x: T, y: T, order: u8) -> <T as Unsigned>::Key {
 /// assert_eq!(y, 0u64);
 ///```
 pub fn h2xy<T: Unsigned>(h: <T as Unsigned>::Key, order: u8) -> (T, T) {
-    // Mapping from hilbert states to 2D coordinates
-    // SHHH => SXXXYYY
-    //   8 bit => 8 bit
-    const LUT_3_REV: [u8; 256] = [
-        64, 1, 9, 136, 16, 88, 89, 209, 18, 90, 91, 211, 139, 202, 194, 67, 4, 76, 77, 197, 70, 7,
-        15, 142, 86, 23, 31, 158, 221, 149, 148, 28, 36, 108, 109, 229, 102, 39, 47, 174, 118, 55,
-        63, 190, 253, 181, 180, 60, 187, 250, 242, 115, 235, 163, 162, 42, 233, 161, 160, 40, 112,
-        49, 57, 184, 0, 72, 73, 193, 66, 3, 11, 138, 82, 19, 27, 154, 217, 145, 144, 24, 96, 33,
-        41, 168, 48, 120, 121, 241, 50, 122, 123, 243, 171, 234, 226, 99, 100, 37, 45, 172, 52,
-        124, 125, 245, 54, 126, 127, 247, 175, 238, 230, 103, 223, 151, 150, 30, 157, 220, 212, 85,
-        141, 204, 196, 69, 6, 78, 79, 199, 255, 183, 182, 62, 189, 252, 244, 117, 173, 236, 228,
-        101, 38, 110, 111, 231, 159, 222, 214, 87, 207, 135, 134, 14, 205, 133, 132, 12, 84, 21,
-        29, 156, 155, 218, 210, 83, 203, 131, 130, 10, 201, 129, 128, 8, 80, 17, 25, 152, 32, 104,
-        105, 225, 98, 35, 43, 170, 114, 51, 59, 186, 249, 177, 176, 56, 191, 254, 246, 119, 239,
-        167, 166, 46, 237, 165, 164, 44, 116, 53, 61, 188, 251, 179, 178, 58, 185, 248, 240, 113,
-        169, 232, 224, 97, 34, 106, 107, 227, 219, 147, 146, 26, 153, 216, 208, 81, 137, 200, 192,
-        65, 2, 74, 75, 195, 68, 5, 13, 140, 20, 92, 93, 213, 22, 94, 95, 215, 143, 206, 198, 71,
-    ];
-    let coor_bits = (size_of::<T>() << 3) as u8;
-    let useless_bits = (h.leading_zeros() >> 1) as u8 & !1;
-    let lowest_order = coor_bits - useless_bits + (order & 1);
+    h2xy_lowest(h, h_lowest_order::<T>(h, order))
+}
 
+/// Run the `LUT_3_REV` state machine for an already computed `lowest_order`.
+#[inline]
+fn h2xy_lowest<T: Unsigned>(h: <T as Unsigned>::Key, lowest_order: u8) -> (T, T) {
     let mut x_result: T = T::ZERO;
     let mut y_result: T = x_result;
 
@@ -287,24 +460,39 @@ pub fn h2xy<T: Unsigned>(h: <T as Unsigned>::Key, order: u8) -> (T, T) {
     (x_result, y_result)
 }
 
+/// Small xorshift generator so the tests do not need an additional dependency
 #[cfg(test)]
-mod tests {
-    // From 2D to 1D
-    // 4 bits => 4 bits
-    const LUT_SXY2SH: [u8; 16] = [4, 1, 11, 2, 0, 15, 5, 6, 10, 9, 3, 12, 14, 7, 13, 8];
-
-    // From 1D to 2D
-    // 4 bits => 4 bits
-    const LUT_SH2SXY: [u8; 16] = [
-        0b0100, 0b0001, 0b0011, 0b1010, //
-        0b0000, 0b0110, 0b0111, 0b1101, //
-        0b1111, 0b1001, 0b1000, 0b0010, //
-        0b1011, 0b1110, 0b1100, 0b0101,
-    ];
+fn xorshift(seed: u64) -> impl FnMut() -> u64 {
+    let mut state = seed;
+    move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    }
+}
 
+#[cfg(test)]
+mod tests {
     use super::*;
     extern crate image;
 
+    #[test]
+    fn inverse_lut() {
+        // From 2D to 1D
+        // 4 bits => 4 bits
+        assert_eq!(
+            LUT_SXY2SH,
+            [4, 1, 11, 2, 0, 15, 5, 6, 10, 9, 3, 12, 14, 7, 13, 8]
+        );
+        for sh in 0..16 {
+            let sxy = LUT_SH2SXY[sh];
+            let back = LUT_SXY2SH[usize::from(sh as u8 & 0b1100 | sxy & 0b11)];
+            assert_eq!(usize::from(back & 0b11), sh & 0b11);
+            assert_eq!(back & 0b1100, sxy & 0b1100);
+        }
+    }
+
     #[test]
     fn gen_lut3_sxxxyyy() {
         // State 0, 1, 2, 3
@@ -0,0 +1,164 @@
+use crate::{assert_order, h2xy, low_bits, xy2h, Unsigned, UnsignedBase};
+
+/// Convert from 2D to 1D space along the closed Moore curve.
+///
+/// The Moore curve joins four hilbert curves of `order - 1` in the quadrants lower left, upper left,
+/// upper right and lower right. It starts at `(2^(order-1) - 1, 0)` and ends at the adjacent cell
+/// `(2^(order-1), 0)`, so the curve forms a loop.
+///
+/// # Arguments
+/// * `x` - Coordinate in 2D space
+/// * `y` - Coordinate in 2D space
+/// * `order` - The curve order, must be at least one
+///
+/// # Panics
+/// If `order` is zero, `order` exceeds the number of bits of `T` or a coordinate is outside of the curve.
+///
+/// # Examples
+///```
+/// let moore = fast_hilbert::xy2moore(1u32, 0, 2);
+/// assert_eq!(moore, 0u64);
+/// let moore = fast_hilbert::xy2moore(2u32, 0, 2);
+/// assert_eq!(moore, 15u64);
+///```
+pub fn xy2moore<T: Unsigned>(x: T, y: T, order: u8) -> T::Key {
+    assert!(
+        order > 0,
+        "the moore curve requires an order of at least one"
+    );
+    assert_order::<T>(order);
+    let grid_max: T = low_bits(order.into());
+    assert!(
+        x <= grid_max && y <= grid_max,
+        "coordinate ({x:?}, {y:?}) is outside of the curve with order {order}"
+    );
+    let sub_order = order - 1;
+    let max: T = low_bits(u32::from(sub_order));
+    let (right, upper) = (x > max, y > max);
+    let (x, y) = (x & max, y & max);
+
+    let (quadrant, u, v) = match (right, upper) {
+        (false, false) => (0, y, max - x),
+        (false, true) => (1, y, max - x),
+        (true, true) => (2, max - y, x),
+        (true, false) => (3, max - y, x),
+    };
+    T::Key::from(quadrant) << (usize::from(sub_order) << 1) | xy2h(u, v, sub_order)
+}
+
+/// Convert from 1D space along the closed Moore curve to 2D coordinates.
+///
+/// Inverse of [`xy2moore`].
+///
+/// # Arguments
+/// * `h` - Coordinate on the moore curve
+/// * `order` - The curve order, must be at least one
+///
+/// # Panics
+/// If `order` is zero, `order` exceeds the number of bits of `T` or `h` exceeds the indices of `order`.
+///
+/// # Examples
+///```
+/// let (x, y) = fast_hilbert::moore2xy::<u32>(15, 2);
+/// assert_eq!((x, y), (2, 0));
+///```
+pub fn moore2xy<T: Unsigned>(h: T::Key, order: u8) -> (T, T) {
+    assert!(
+        order > 0,
+        "the moore curve requires an order of at least one"
+    );
+    assert_order::<T>(order);
+    assert!(
+        h <= low_bits(u32::from(order) << 1),
+        "index {h:?} exceeds the indices of order {order}"
+    );
+    let sub_order = order - 1;
+    let max: T = low_bits(u32::from(sub_order));
+    let shift = usize::from(sub_order) << 1;
+    let quadrant = (h >> shift).as_u8() & 3;
+    let (u, v) = h2xy::<T>(h & low_bits(u32::from(sub_order) << 1), sub_order);
+
+    // The coordinates within a quadrant are below `half`, so adding it is setting its bit
+    let half = T::from(1) << usize::from(sub_order);
+    match quadrant {
+        0 => (max - v, u),
+        1 => (max - v, u | half),
+        2 => (v | half, (max - u) | half),
+        _ => (v | half, max - u),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn moore_and_rev() {
+        for order in 1..=6u8 {
+            let n = 1u16 << order;
+            let mut seen = vec![false; usize::from(n) * usize::from(n)];
+            for x in 0..n {
+                for y in 0..n {
+                    let h = xy2moore(x, y, order);
+                    assert_eq!(moore2xy::<u16>(h, order), (x, y));
+                    assert!(!core::mem::replace(&mut seen[h as usize], true));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn moore_adjacent_and_closed() {
+        let distance =
+            |(ax, ay): (u16, u16), (bx, by): (u16, u16)| ax.abs_diff(bx) + ay.abs_diff(by);
+        for order in 1..=7u8 {
+            let last = (1u32 << (2 * order)) - 1;
+            for h in 0..last {
+                let a = moore2xy::<u16>(h, order);
+                let b = moore2xy::<u16>(h + 1, order);
+                assert_eq!(distance(a, b), 1, "order {order} h {h}");
+            }
+            let first = moore2xy::<u16>(0, order);
+            let end = moore2xy::<u16>(last, order);
+            assert_eq!(distance(first, end), 1, "order {order}");
+        }
+    }
+
+    #[test]
+    fn moore_full_order() {
+        for (x, y) in [
+            (0, 0),
+            (u32::MAX, 0),
+            (0, u32::MAX),
+            (u32::MAX, u32::MAX),
+            (1 << 31, 12345),
+        ] {
+            assert_eq!(moore2xy::<u32>(xy2moore(x, y, 32), 32), (x, y));
+        }
+        assert_eq!(moore2xy::<u64>(u128::MAX, 64), (1 << 63, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds the number of bits")]
+    fn moore_order_beyond_type() {
+        let _ = xy2moore(0u8, 0, 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "outside of the curve")]
+    fn moore_outside_of_curve() {
+        let _ = xy2moore(9u32, 0, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds the number of bits")]
+    fn moore_rev_order_beyond_type() {
+        let _ = moore2xy::<u8>(0, 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds the indices of order")]
+    fn moore_rev_index_beyond_order() {
+        let _ = moore2xy::<u32>(16, 2);
+    }
+}
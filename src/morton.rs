@@ -0,0 +1,130 @@
+use crate::{low_bits, Unsigned, UnsignedBase};
+
+/// Convert from 2D to 1D space along the Z-order (Morton) curve.
+///
+/// The bits of `x` are placed at the even and the bits of `y` at the odd positions of the key.
+/// Contrary to the hilbert curve no order is needed, because leading zero bits do not change the key.
+///
+/// # Arguments
+/// * `x` - Coordinate in 2D space
+/// * `y` - Coordinate in 2D space
+///
+/// # Examples
+///```
+/// let morton = fast_hilbert::xy2morton(0b11u32, 0b01);
+/// assert_eq!(morton, 0b0111u64);
+///```
+pub fn xy2morton<T: Unsigned>(x: T, y: T) -> T::Key {
+    spread(x) | spread(y) << 1usize
+}
+
+/// Convert from 1D space along the Z-order (Morton) curve to 2D coordinates.
+///
+/// # Arguments
+/// * `h` - Coordinate on the morton curve
+///
+/// # Examples
+///```
+/// let (x, y) = fast_hilbert::morton2xy::<u32>(0b0111);
+/// assert_eq!((x, y), (0b11, 0b01));
+///```
+pub fn morton2xy<T: Unsigned>(h: T::Key) -> (T, T) {
+    (compact(h), compact(h >> 1usize))
+}
+
+/// Masks with alternating groups of set and cleared bits as used by the spreading steps.
+///
+/// The first mask belongs to the widest shift of a quarter of the key bits, the last one to the shift by one bit.
+/// Returns the masks and their count.
+#[inline]
+fn masks<K: UnsignedBase>() -> ([K; 6], usize) {
+    let key_bits = size_of::<K>() << 3;
+    let mut masks = [K::ZERO; 6];
+    let mut mask: K = low_bits((key_bits >> 1) as u32);
+    let mut shift = key_bits >> 2;
+    let mut count = 0;
+    while shift > 0 {
+        // Clear the bits in which the mask overlaps with its shifted copy
+        mask = (mask | mask << shift) & !(mask & mask << shift);
+        masks[count] = mask;
+        count += 1;
+        shift >>= 1;
+    }
+    (masks, count)
+}
+
+/// Insert a zero bit in front of every bit of `v`.
+#[inline]
+fn spread<T: Unsigned>(v: T) -> T::Key {
+    let (masks, count) = masks::<T::Key>();
+    let mut result: T::Key = v.into();
+    let mut shift = size_of::<T>() << 2;
+    for &mask in &masks[..count] {
+        result = (result | result << shift) & mask;
+        shift >>= 1;
+    }
+    result
+}
+
+/// Inverse of [`spread`] which drops every odd bit of `h`.
+#[inline]
+fn compact<T: Unsigned>(h: T::Key) -> T {
+    let (masks, count) = masks::<T::Key>();
+    let mut result = h & masks[count - 1];
+    let mut shift = 1usize;
+    for &mask in masks[..count - 1].iter().rev() {
+        result = (result | result >> shift) & mask;
+        shift <<= 1;
+    }
+    result = result | result >> shift;
+
+    // Only the lower half of the key is set, which is collected byte by byte
+    let mut out = T::ZERO;
+    for shift in (0..size_of::<T>()).map(|byte| byte << 3) {
+        out |= T::from((result >> shift).as_u8()) << shift;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xorshift;
+
+    /// Interleave bit by bit as reference
+    fn naive(x: u64, y: u64) -> u128 {
+        (0..64).fold(0, |h, bit| {
+            h | u128::from(x >> bit & 1) << (2 * bit) | u128::from(y >> bit & 1) << (2 * bit + 1)
+        })
+    }
+
+    #[test]
+    fn morton_and_rev() {
+        let mut next = xorshift(0x9e37_79b9_7f4a_7c15);
+        for _ in 0..1000 {
+            let (x, y) = (next(), next());
+            let h = xy2morton(x, y);
+            assert_eq!(h, naive(x, y));
+            assert_eq!(morton2xy::<u64>(h), (x, y));
+
+            let (x, y) = (x as u32, y as u32);
+            assert_eq!(u128::from(xy2morton(x, y)), naive(x.into(), y.into()));
+            assert_eq!(morton2xy::<u32>(xy2morton(x, y)), (x, y));
+
+            let (x, y) = (x as u16, y as u16);
+            assert_eq!(morton2xy::<u16>(xy2morton(x, y)), (x, y));
+
+            let (x, y) = (x as u8, y as u8);
+            assert_eq!(u128::from(xy2morton(x, y)), naive(x.into(), y.into()));
+            assert_eq!(morton2xy::<u8>(xy2morton(x, y)), (x, y));
+        }
+    }
+
+    #[test]
+    fn morton_extremes() {
+        assert_eq!(xy2morton(u64::MAX, 0), u128::MAX / 3);
+        assert_eq!(xy2morton(u64::MAX, u64::MAX), u128::MAX);
+        assert_eq!(morton2xy::<u8>(0b10), (0, 1));
+        assert_eq!(morton2xy::<u32>(u64::MAX), (u32::MAX, u32::MAX));
+    }
+}
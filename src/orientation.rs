@@ -0,0 +1,347 @@
+use crate::{assert_order, h2xy, low_bits, xy2h, Unsigned};
+
+/// Start and end corner of a hilbert curve.
+///
+/// The curve always connects two adjacent corners of the square, which results in eight symmetric variants.
+/// [`xy2h`] always runs from the lower left to the lower right corner, independent of the order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Orientation {
+    /// Start at `(0, 0)` and end at `(max, 0)`, which is the curve of [`xy2h`]
+    #[default]
+    LowerLeftToLowerRight,
+    /// Start at `(0, 0)` and end at `(0, max)`
+    LowerLeftToUpperLeft,
+    /// Start at `(max, 0)` and end at `(0, 0)`
+    LowerRightToLowerLeft,
+    /// Start at `(max, 0)` and end at `(max, max)`
+    LowerRightToUpperRight,
+    /// Start at `(0, max)` and end at `(max, max)`
+    UpperLeftToUpperRight,
+    /// Start at `(0, max)` and end at `(0, 0)`
+    UpperLeftToLowerLeft,
+    /// Start at `(max, max)` and end at `(0, max)`
+    UpperRightToUpperLeft,
+    /// Start at `(max, max)` and end at `(max, 0)`
+    UpperRightToLowerRight,
+}
+
+impl Orientation {
+    /// All eight orientations
+    pub const ALL: [Orientation; 8] = [
+        Orientation::LowerLeftToLowerRight,
+        Orientation::LowerLeftToUpperLeft,
+        Orientation::LowerRightToLowerLeft,
+        Orientation::LowerRightToUpperRight,
+        Orientation::UpperLeftToUpperRight,
+        Orientation::UpperLeftToLowerLeft,
+        Orientation::UpperRightToUpperLeft,
+        Orientation::UpperRightToLowerRight,
+    ];
+
+    /// Map coordinates into the frame of [`xy2h`]
+    fn xy_to_canonical<T: Unsigned>(self, x: T, y: T, max: T) -> (T, T) {
+        match self {
+            Orientation::LowerLeftToLowerRight => (x, y),
+            Orientation::LowerLeftToUpperLeft => (y, x),
+            Orientation::LowerRightToLowerLeft => (max - x, y),
+            Orientation::LowerRightToUpperRight => (y, max - x),
+            Orientation::UpperLeftToUpperRight => (x, max - y),
+            Orientation::UpperLeftToLowerLeft => (max - y, x),
+            Orientation::UpperRightToUpperLeft => (max - x, max - y),
+            Orientation::UpperRightToLowerRight => (max - y, max - x),
+        }
+    }
+
+    /// Inverse of [`Orientation::xy_to_canonical`]
+    fn canonical_to_xy<T: Unsigned>(self, u: T, v: T, max: T) -> (T, T) {
+        match self {
+            Orientation::LowerLeftToLowerRight => (u, v),
+            Orientation::LowerLeftToUpperLeft => (v, u),
+            Orientation::LowerRightToLowerLeft => (max - u, v),
+            Orientation::LowerRightToUpperRight => (max - v, u),
+            Orientation::UpperLeftToUpperRight => (u, max - v),
+            Orientation::UpperLeftToLowerLeft => (v, max - u),
+            Orientation::UpperRightToUpperLeft => (max - u, max - v),
+            Orientation::UpperRightToLowerRight => (max - v, max - u),
+        }
+    }
+}
+
+/// Axis along which the curve leaves the origin.
+///
+/// [`xy2h`] only uses `order` to decide this axis. For even orders the first step is along the x-axis
+/// and for odd orders along the y-axis. Apart from that the order has no influence on the result, which is
+/// why `xy2h(1, 0, 3)` equals `xy2h(1, 0, 5)` but not `xy2h(1, 0, 4)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Axis {
+    /// First step from `(0, 0)` to `(1, 0)`
+    X,
+    /// First step from `(0, 0)` to `(0, 1)`
+    Y,
+}
+
+impl Axis {
+    /// Axis of the first step of the curve which [`xy2h`] uses for `order`.
+    ///
+    /// # Examples
+    ///```
+    /// use fast_hilbert::Axis;
+    ///
+    /// assert_eq!(Axis::of_order(4), Axis::X);
+    /// assert_eq!(Axis::of_order(5), Axis::Y);
+    ///```
+    #[must_use]
+    pub fn of_order(order: u8) -> Self {
+        if order & 1 == 0 {
+            Axis::X
+        } else {
+            Axis::Y
+        }
+    }
+
+    /// Smallest order which results in this axis
+    fn order(self) -> u8 {
+        match self {
+            Axis::X => 0,
+            Axis::Y => 1,
+        }
+    }
+}
+
+/// Convert from 2D to 1D hilbert space without a limiting order.
+///
+/// The curve grows with the coordinates, so all coordinates of the type are valid. Only the axis of the
+/// first step needs to be chosen. For any `order` and any coordinates the result equals
+/// `xy2h(x, y, order)` with `first_step` being [`Axis::of_order(order)`](Axis::of_order).
+///
+/// # Arguments
+/// * `x` - Coordinate in 2D space
+/// * `y` - Coordinate in 2D space
+/// * `first_step` - Axis along which the curve leaves the origin
+///
+/// # Examples
+///```
+/// use fast_hilbert::Axis;
+///
+/// assert_eq!(fast_hilbert::xy2h_unbounded(1u32, 0, Axis::X), 1u64);
+/// assert_eq!(fast_hilbert::xy2h_unbounded(1u32, 0, Axis::Y), 3u64);
+/// assert_eq!(fast_hilbert::xy2h_unbounded(1u32, 0, Axis::Y), fast_hilbert::xy2h(1u32, 0, 5));
+///```
+pub fn xy2h_unbounded<T: Unsigned>(x: T, y: T, first_step: Axis) -> T::Key {
+    xy2h(x, y, first_step.order())
+}
+
+/// Convert from 1D hilbert space to 2D coordinates without a limiting order.
+///
+/// Inverse of [`xy2h_unbounded`].
+///
+/// # Arguments
+/// * `h` - Coordinate in 1D hilbert space
+/// * `first_step` - Axis along which the curve leaves the origin
+///
+/// # Examples
+///```
+/// use fast_hilbert::Axis;
+///
+/// assert_eq!(fast_hilbert::h2xy_unbounded::<u32>(1, Axis::X), (1, 0));
+/// assert_eq!(fast_hilbert::h2xy_unbounded::<u32>(1, Axis::Y), (0, 1));
+///```
+pub fn h2xy_unbounded<T: Unsigned>(h: T::Key, first_step: Axis) -> (T, T) {
+    h2xy(h, first_step.order())
+}
+
+/// Convert from 2D to 1D hilbert space for a curve with the given start and end corner.
+///
+/// The coordinates must be within the square of `order`, because the corners depend on its size.
+///
+/// # Arguments
+/// * `x` - Coordinate in 2D space
+/// * `y` - Coordinate in 2D space
+/// * `order` - The hilbert curve order
+/// * `orientation` - Start and end corner of the curve
+///
+/// # Panics
+/// If `order` exceeds the number of bits of `T` or a coordinate is outside of the curve.
+///
+/// # Examples
+///```
+/// use fast_hilbert::Orientation;
+///
+/// let h = fast_hilbert::xy2h_oriented(3u32, 3, 2, Orientation::UpperRightToLowerRight);
+/// assert_eq!(h, 0u64);
+/// let h = fast_hilbert::xy2h_oriented(3u32, 0, 2, Orientation::UpperRightToLowerRight);
+/// assert_eq!(h, 15u64);
+///```
+pub fn xy2h_oriented<T: Unsigned>(x: T, y: T, order: u8, orientation: Orientation) -> T::Key {
+    assert_order::<T>(order);
+    let max: T = low_bits(order.into());
+    assert!(
+        x <= max && y <= max,
+        "coordinate ({x:?}, {y:?}) is outside of the curve with order {order}"
+    );
+    let (u, v) = orientation.xy_to_canonical(x, y, max);
+    xy2h(u, v, order)
+}
+
+/// Convert from 1D hilbert space to 2D coordinates for a curve with the given start and end corner.
+///
+/// Inverse of [`xy2h_oriented`].
+///
+/// # Arguments
+/// * `h` - Coordinate in 1D hilbert space
+/// * `order` - The hilbert curve order
+/// * `orientation` - Start and end corner of the curve
+///
+/// # Panics
+/// If `order` exceeds the number of bits of `T` or `h` exceeds the indices of `order`.
+///
+/// # Examples
+///```
+/// use fast_hilbert::Orientation;
+///
+/// let (x, y) = fast_hilbert::h2xy_oriented::<u32>(0, 2, Orientation::UpperRightToLowerRight);
+/// assert_eq!((x, y), (3, 3));
+///```
+pub fn h2xy_oriented<T: Unsigned>(h: T::Key, order: u8, orientation: Orientation) -> (T, T) {
+    assert_order::<T>(order);
+    assert!(
+        h <= low_bits(u32::from(order) << 1),
+        "index {h:?} exceeds the indices of order {order}"
+    );
+    let (u, v) = h2xy(h, order);
+    orientation.canonical_to_xy(u, v, low_bits(order.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Start and end corner as `(x, y)` with `x` and `y` being either zero or one
+    fn corners(orientation: Orientation) -> ((u16, u16), (u16, u16)) {
+        match orientation {
+            Orientation::LowerLeftToLowerRight => ((0, 0), (1, 0)),
+            Orientation::LowerLeftToUpperLeft => ((0, 0), (0, 1)),
+            Orientation::LowerRightToLowerLeft => ((1, 0), (0, 0)),
+            Orientation::LowerRightToUpperRight => ((1, 0), (1, 1)),
+            Orientation::UpperLeftToUpperRight => ((0, 1), (1, 1)),
+            Orientation::UpperLeftToLowerLeft => ((0, 1), (0, 0)),
+            Orientation::UpperRightToUpperLeft => ((1, 1), (0, 1)),
+            Orientation::UpperRightToLowerRight => ((1, 1), (1, 0)),
+        }
+    }
+
+    #[test]
+    fn start_and_end_corners() {
+        for orientation in Orientation::ALL {
+            let (start, end) = corners(orientation);
+            for order in 1..=8u8 {
+                let max = (1u16 << order) - 1;
+                let last = (1u32 << (2 * order)) - 1;
+                assert_eq!(
+                    h2xy_oriented::<u16>(0, order, orientation),
+                    (start.0 * max, start.1 * max),
+                    "{orientation:?} order {order}"
+                );
+                assert_eq!(
+                    h2xy_oriented::<u16>(last, order, orientation),
+                    (end.0 * max, end.1 * max),
+                    "{orientation:?} order {order}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn oriented_and_rev() {
+        for orientation in Orientation::ALL {
+            for order in 1..=5u8 {
+                let n = 1u8 << order;
+                let mut prev = h2xy_oriented::<u8>(0, order, orientation);
+                for h in 0..u16::from(n) * u16::from(n) {
+                    let (x, y) = h2xy_oriented::<u8>(h, order, orientation);
+                    assert_eq!(xy2h_oriented(x, y, order, orientation), h);
+                    assert!(x.abs_diff(prev.0) + y.abs_diff(prev.1) <= 1);
+                    prev = (x, y);
+                }
+            }
+            let (x, y) = (u64::MAX, 12345);
+            let h = xy2h_oriented(x, y, 64, orientation);
+            assert_eq!(h2xy_oriented::<u64>(h, 64, orientation), (x, y));
+        }
+    }
+
+    #[test]
+    fn unbounded_matches_xy2h() {
+        let values = [
+            0u32,
+            1,
+            2,
+            3,
+            7,
+            100,
+            12345,
+            1 << 20,
+            u32::MAX >> 1,
+            u32::MAX,
+        ];
+        for order in 0..=32u8 {
+            let axis = Axis::of_order(order);
+            for x in values {
+                for y in values {
+                    let h = xy2h(x, y, order);
+                    assert_eq!(xy2h_unbounded(x, y, axis), h);
+                    assert_eq!(h2xy_unbounded::<u32>(h, axis), (x, y));
+                    assert_eq!(h2xy::<u32>(h, order), (x, y));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn unbounded_first_step() {
+        assert_eq!(h2xy_unbounded::<u8>(0, Axis::X), (0, 0));
+        assert_eq!(h2xy_unbounded::<u8>(1, Axis::X), (1, 0));
+        assert_eq!(h2xy_unbounded::<u8>(0, Axis::Y), (0, 0));
+        assert_eq!(h2xy_unbounded::<u8>(1, Axis::Y), (0, 1));
+        // Squares of the same parity as the axis are covered by a prefix of the curve
+        for (axis, order) in [(Axis::X, 4), (Axis::Y, 3)] {
+            let n = 1u16 << order;
+            for x in 0..n {
+                for y in 0..n {
+                    assert!(xy2h_unbounded(x, y, axis) < u32::from(n) * u32::from(n));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn default_is_xy2h() {
+        for order in 1..=6u8 {
+            let n = 1u32 << order;
+            for (x, y) in (0..n).flat_map(|x| (0..n).map(move |y| (x, y))) {
+                assert_eq!(
+                    xy2h_oriented(x, y, order, Orientation::default()),
+                    xy2h(x, y, order)
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "outside of the curve")]
+    fn oriented_outside_of_curve() {
+        let _ = xy2h_oriented(9u32, 0, 3, Orientation::LowerRightToLowerLeft);
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds the indices of order")]
+    fn oriented_index_beyond_order() {
+        let _ = h2xy_oriented::<u32>(64, 3, Orientation::UpperRightToUpperLeft);
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds the number of bits")]
+    fn oriented_order_beyond_type() {
+        let _ = h2xy_oriented::<u8>(0, 9, Orientation::LowerLeftToUpperLeft);
+    }
+}
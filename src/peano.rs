@@ -0,0 +1,233 @@
+use crate::{UnsignedBase, UnsignedCurve};
+
+// Mapping from state and two ternary digits of each coordinate to the next state and two peano digits
+// S * 81 + XX * 9 + YY => S * 81 + PP
+const LUT_PEANO: [u16; 324] = [
+    0, 163, 2, 177, 16, 179, 18, 181, 20, 86, 247, 84, 257, 94, 255, 104, 265, 102, 6, 169, 8, 171,
+    10, 173, 24, 187, 26, 128, 289, 126, 287, 124, 285, 110, 271, 108, 48, 211, 50, 201, 40, 203,
+    30, 193, 32, 134, 295, 132, 281, 118, 279, 116, 277, 114, 54, 217, 56, 231, 70, 233, 72, 235,
+    74, 140, 301, 138, 311, 148, 309, 158, 319, 156, 60, 223, 62, 225, 64, 227, 78, 241, 80, 101,
+    262, 99, 260, 97, 258, 83, 244, 81, 21, 184, 23, 174, 13, 176, 3, 166, 5, 107, 268, 105, 254,
+    91, 252, 89, 250, 87, 27, 190, 29, 204, 43, 206, 45, 208, 47, 113, 274, 111, 284, 121, 282,
+    131, 292, 129, 33, 196, 35, 198, 37, 200, 51, 214, 53, 155, 316, 153, 314, 151, 312, 137, 298,
+    135, 75, 238, 77, 228, 67, 230, 57, 220, 59, 161, 322, 159, 308, 145, 306, 143, 304, 141, 222,
+    61, 224, 63, 226, 65, 240, 79, 242, 302, 139, 300, 149, 310, 147, 320, 157, 318, 216, 55, 218,
+    69, 232, 71, 234, 73, 236, 296, 133, 294, 119, 280, 117, 278, 115, 276, 210, 49, 212, 39, 202,
+    41, 192, 31, 194, 290, 127, 288, 125, 286, 123, 272, 109, 270, 168, 7, 170, 9, 172, 11, 186,
+    25, 188, 248, 85, 246, 95, 256, 93, 266, 103, 264, 162, 1, 164, 15, 178, 17, 180, 19, 182, 323,
+    160, 321, 146, 307, 144, 305, 142, 303, 237, 76, 239, 66, 229, 68, 219, 58, 221, 317, 154, 315,
+    152, 313, 150, 299, 136, 297, 195, 34, 197, 36, 199, 38, 213, 52, 215, 275, 112, 273, 122, 283,
+    120, 293, 130, 291, 189, 28, 191, 42, 205, 44, 207, 46, 209, 269, 106, 267, 92, 253, 90, 251,
+    88, 249, 183, 22, 185, 12, 175, 14, 165, 4, 167, 263, 100, 261, 98, 259, 96, 245, 82, 243,
+];
+
+// Mapping from state and two peano digits to the next state and two ternary digits of each coordinate
+// S * 81 + PP => S * 81 + XX * 9 + YY
+const LUT_PEANO_REV: [u16; 324] = [
+    0, 163, 2, 92, 253, 90, 18, 181, 20, 183, 22, 185, 257, 94, 255, 165, 4, 167, 6, 169, 8, 98,
+    259, 96, 24, 187, 26, 116, 277, 114, 42, 205, 44, 134, 295, 132, 293, 130, 291, 201, 40, 203,
+    275, 112, 273, 110, 271, 108, 36, 199, 38, 128, 289, 126, 54, 217, 56, 146, 307, 144, 72, 235,
+    74, 237, 76, 239, 311, 148, 309, 219, 58, 221, 60, 223, 62, 152, 313, 150, 78, 241, 80, 89,
+    250, 87, 15, 178, 17, 107, 268, 105, 266, 103, 264, 174, 13, 176, 248, 85, 246, 83, 244, 81, 9,
+    172, 11, 101, 262, 99, 27, 190, 29, 119, 280, 117, 45, 208, 47, 210, 49, 212, 284, 121, 282,
+    192, 31, 194, 33, 196, 35, 125, 286, 123, 51, 214, 53, 143, 304, 141, 69, 232, 71, 161, 322,
+    159, 320, 157, 318, 228, 67, 230, 302, 139, 300, 137, 298, 135, 63, 226, 65, 155, 316, 153,
+    234, 73, 236, 308, 145, 306, 216, 55, 218, 57, 220, 59, 149, 310, 147, 75, 238, 77, 240, 79,
+    242, 314, 151, 312, 222, 61, 224, 296, 133, 294, 204, 43, 206, 278, 115, 276, 113, 274, 111,
+    39, 202, 41, 131, 292, 129, 290, 127, 288, 198, 37, 200, 272, 109, 270, 180, 19, 182, 254, 91,
+    252, 162, 1, 164, 3, 166, 5, 95, 256, 93, 21, 184, 23, 186, 25, 188, 260, 97, 258, 168, 7, 170,
+    323, 160, 321, 231, 70, 233, 305, 142, 303, 140, 301, 138, 66, 229, 68, 158, 319, 156, 317,
+    154, 315, 225, 64, 227, 299, 136, 297, 207, 46, 209, 281, 118, 279, 189, 28, 191, 30, 193, 32,
+    122, 283, 120, 48, 211, 50, 213, 52, 215, 287, 124, 285, 195, 34, 197, 269, 106, 267, 177, 16,
+    179, 251, 88, 249, 86, 247, 84, 12, 175, 14, 104, 265, 102, 263, 100, 261, 171, 10, 173, 245,
+    82, 243,
+];
+
+/// Side length `3^n` of the largest peano curve whose indices fit into the key type.
+#[inline]
+fn side_length<T: UnsignedCurve>() -> T {
+    // floor(bits * log3(2)) is exactly five digits per byte for all supported types
+    (0..size_of::<T>() * 5).fold(T::from(1), |side, _| side * T::from(3))
+}
+
+/// Convert from 2D to 1D space along the Peano curve.
+///
+/// The curve traverses a grid with a side length of a power of three. Like the hilbert curve it starts at the
+/// origin, but it ends in the opposite corner. Leading zero digits do not change the curve, so no order is needed.
+///
+/// # Arguments
+/// * `x` - Coordinate in 2D space
+/// * `y` - Coordinate in 2D space
+///
+/// # Panics
+/// If a coordinate does not fit into the largest curve of the key type, which has a side length of
+/// `3^5` for `u8`, `3^10` for `u16`, `3^20` for `u32` and `3^40` for `u64`.
+///
+/// # Examples
+///```
+/// let peano = fast_hilbert::xy2peano(1u32, 0);
+/// assert_eq!(peano, 5u64);
+///```
+pub fn xy2peano<T: UnsignedCurve>(x: T, y: T) -> T::Key {
+    let side = side_length::<T>();
+    assert!(
+        x < side && y < side,
+        "coordinates exceed the peano curve of the key type"
+    );
+
+    // Base 9 digits of both coordinates from the least to the most significant
+    let nine = T::from(9);
+    let mut digits = [(0, 0); 20];
+    let mut count = 0;
+    let (mut x, mut y) = (x, y);
+    while x | y != T::ZERO {
+        digits[count] = ((x % nine).as_usize(), (y % nine).as_usize());
+        (x, y) = (x / nine, y / nine);
+        count += 1;
+    }
+
+    let base = T::Key::from(81);
+    let mut state = 0;
+    let mut result = T::Key::ZERO;
+    for &(xx, yy) in digits[..count].iter().rev() {
+        let r = LUT_PEANO[state + xx * 9 + yy];
+        state = usize::from(r / 81) * 81;
+        result = result * base + T::Key::from((r % 81) as u8);
+    }
+    result
+}
+
+/// Convert from 1D space along the Peano curve to 2D coordinates.
+///
+/// Inverse of [`xy2peano`].
+///
+/// # Arguments
+/// * `h` - Coordinate on the peano curve
+///
+/// # Panics
+/// If `h` exceeds the largest curve of the key type.
+///
+/// # Examples
+///```
+/// let (x, y) = fast_hilbert::peano2xy::<u32>(5);
+/// assert_eq!((x, y), (1, 0));
+///```
+pub fn peano2xy<T: UnsignedCurve>(h: T::Key) -> (T, T) {
+    let side: T::Key = side_length::<T>().into();
+    assert!(
+        h < side * side,
+        "index exceeds the peano curve of the key type"
+    );
+
+    // Base 81 digits from the least to the most significant
+    let base = T::Key::from(81);
+    let mut digits = [0; 20];
+    let mut count = 0;
+    let mut h = h;
+    while h != T::Key::ZERO {
+        digits[count] = (h % base).as_usize();
+        h = h / base;
+        count += 1;
+    }
+
+    let nine = T::from(9);
+    let mut state = 0;
+    let (mut x, mut y) = (T::ZERO, T::ZERO);
+    for &pp in digits[..count].iter().rev() {
+        let r = LUT_PEANO_REV[state + pp];
+        state = usize::from(r / 81) * 81;
+        let xy = (r % 81) as u8;
+        x = x * nine + T::from(xy / 9);
+        y = y * nine + T::from(xy % 9);
+    }
+    (x, y)
+}
+
+#[cfg(test)]
+mod tests {
+    // Single ternary digit of the state machine. The state holds whether x and y are mirrored.
+    // S * 9 + X * 3 + Y => S * 9 + P
+    const LUT_SXY2SP: [u8; 36] = [
+        0, 19, 2, 14, 31, 12, 6, 25, 8, 11, 28, 9, 3, 22, 5, 17, 34, 15, 24, 7, 26, 32, 13, 30, 18,
+        1, 20, 35, 16, 33, 21, 4, 23, 29, 10, 27,
+    ];
+
+    use super::*;
+
+    #[test]
+    fn gen_lut_peano() {
+        let mut lut = [0u16; 324];
+        for state in 0..4 {
+            for xx in 0..9 {
+                for yy in 0..9 {
+                    let upper = LUT_SXY2SP[state * 9 + xx / 3 * 3 + yy / 3];
+                    let lower = LUT_SXY2SP[usize::from(upper / 9) * 9 + xx % 3 * 3 + yy % 3];
+                    lut[state * 81 + xx * 9 + yy] =
+                        u16::from(lower / 9) * 81 + u16::from(upper % 9) * 9 + u16::from(lower % 9);
+                }
+            }
+        }
+        assert_eq!(lut, LUT_PEANO);
+    }
+
+    #[test]
+    fn gen_lut_peano_rev() {
+        let mut lut_sp2sxy = [0u8; 36];
+        for (input, &r) in LUT_SXY2SP.iter().enumerate() {
+            lut_sp2sxy[input / 9 * 9 + usize::from(r % 9)] = r / 9 * 9 + (input % 9) as u8;
+        }
+
+        let mut lut = [0u16; 324];
+        for state in 0..4 {
+            for pp in 0..81 {
+                let upper = lut_sp2sxy[state * 9 + pp / 9];
+                let lower = lut_sp2sxy[usize::from(upper / 9) * 9 + pp % 9];
+                let xx = u16::from(upper % 9 / 3 * 3 + lower % 9 / 3);
+                let yy = u16::from(upper % 3 * 3 + lower % 3);
+                lut[state * 81 + pp] = u16::from(lower / 9) * 81 + xx * 9 + yy;
+            }
+        }
+        assert_eq!(lut, LUT_PEANO_REV);
+    }
+
+    #[test]
+    fn peano_and_rev() {
+        for x in 0..243u16 {
+            for y in 0..243u16 {
+                let h = xy2peano(x, y);
+                assert_eq!(peano2xy::<u16>(h), (x, y));
+                assert_eq!(xy2peano(x as u8, y as u8), u16::try_from(h).unwrap());
+            }
+        }
+        let max = side_length::<u64>() - 1;
+        for (x, y) in [(max, 0), (0, max), (max, max), (max / 2, max / 3)] {
+            assert_eq!(peano2xy::<u64>(xy2peano(x, y)), (x, y));
+        }
+        assert_eq!(xy2peano(max, max), u128::from(max) * u128::from(max + 2));
+    }
+
+    #[test]
+    fn peano_adjacent() {
+        for digits in 1..=6 {
+            let side = 3u32.pow(digits);
+            let mut prev = peano2xy::<u32>(0);
+            assert_eq!(prev, (0, 0));
+            for h in 1..u64::from(side * side) {
+                let next = peano2xy::<u32>(h);
+                assert_eq!(
+                    prev.0.abs_diff(next.0) + prev.1.abs_diff(next.1),
+                    1,
+                    "h {h}"
+                );
+                prev = next;
+            }
+            assert_eq!(prev, (side - 1, side - 1));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "exceed the peano curve")]
+    fn peano_out_of_range() {
+        xy2peano(243u8, 0);
+    }
+}
@@ -0,0 +1,288 @@
+use crate::{h2xy, low_bits, xy2h};
+
+/// Errors of the [`HilbertQuantizer`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QuantizeError {
+    /// The order exceeds the 32 bits of the grid coordinates
+    InvalidOrder { order: u8 },
+    /// The bounding box is empty or not finite
+    InvalidBounds {
+        min_x: f64,
+        min_y: f64,
+        max_x: f64,
+        max_y: f64,
+    },
+    /// A coordinate is NaN
+    NotANumber,
+    /// A coordinate lies outside of the bounding box
+    OutOfBounds { x: f64, y: f64 },
+}
+
+impl core::fmt::Display for QuantizeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            QuantizeError::InvalidOrder { order } => {
+                write!(f, "order {order} exceeds the maximum order 32")
+            }
+            QuantizeError::InvalidBounds {
+                min_x,
+                min_y,
+                max_x,
+                max_y,
+            } => write!(
+                f,
+                "bounding box ({min_x}, {min_y}) to ({max_x}, {max_y}) is empty or not finite"
+            ),
+            QuantizeError::NotANumber => write!(f, "coordinate is NaN"),
+            QuantizeError::OutOfBounds { x, y } => {
+                write!(f, "coordinate ({x}, {y}) is outside of the bounding box")
+            }
+        }
+    }
+}
+
+impl core::error::Error for QuantizeError {}
+
+/// Maps floating point coordinates within a bounding box onto a hilbert curve.
+///
+/// The bounding box is divided into `2^order` x `2^order` cells which are encoded with [`xy2h`] for `u32`
+/// coordinates. The maximum of the bounding box is inclusive and belongs to the last cell.
+///
+/// # Examples
+///```
+/// use fast_hilbert::HilbertQuantizer;
+///
+/// let quantizer = HilbertQuantizer::new(-180.0, -90.0, 180.0, 90.0, 16).unwrap();
+/// let h = quantizer.encode(13.4, 52.5).unwrap();
+/// let (lon, lat) = quantizer.decode(h);
+/// let (width, height) = quantizer.cell_size();
+/// assert!((lon - 13.4).abs() <= width / 2.0 && (lat - 52.5).abs() <= height / 2.0);
+///```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HilbertQuantizer {
+    min_x: f64,
+    min_y: f64,
+    max_x: f64,
+    max_y: f64,
+    order: u8,
+    cell_width: f64,
+    cell_height: f64,
+}
+
+impl HilbertQuantizer {
+    /// Create a quantizer for the bounding box from `(min_x, min_y)` to `(max_x, max_y)`.
+    ///
+    /// # Errors
+    /// [`QuantizeError::InvalidOrder`] if `order` exceeds 32 and [`QuantizeError::InvalidBounds`]
+    /// if the bounds or their extent are not finite or the minimum is not smaller than the maximum.
+    pub fn new(
+        min_x: f64,
+        min_y: f64,
+        max_x: f64,
+        max_y: f64,
+        order: u8,
+    ) -> Result<Self, QuantizeError> {
+        if order > 32 {
+            return Err(QuantizeError::InvalidOrder { order });
+        }
+        // The extent of very large bounds can overflow to infinity, which would make every cell infinitely large
+        let finite = [min_x, min_y, max_x, max_y, max_x - min_x, max_y - min_y]
+            .iter()
+            .all(|v| v.is_finite());
+        if !finite || min_x >= max_x || min_y >= max_y {
+            return Err(QuantizeError::InvalidBounds {
+                min_x,
+                min_y,
+                max_x,
+                max_y,
+            });
+        }
+        let cells = (0..order).fold(1.0, |cells, _| cells * 2.0);
+        Ok(Self {
+            min_x,
+            min_y,
+            max_x,
+            max_y,
+            order,
+            cell_width: (max_x - min_x) / cells,
+            cell_height: (max_y - min_y) / cells,
+        })
+    }
+
+    /// The hilbert curve order
+    #[must_use]
+    pub fn order(&self) -> u8 {
+        self.order
+    }
+
+    /// Width and height of a single cell
+    #[must_use]
+    pub fn cell_size(&self) -> (f64, f64) {
+        (self.cell_width, self.cell_height)
+    }
+
+    /// Convert a point within the bounding box to its hilbert index.
+    ///
+    /// # Errors
+    /// [`QuantizeError::NotANumber`] if a coordinate is NaN and [`QuantizeError::OutOfBounds`] if the point
+    /// is outside of the bounding box.
+    pub fn encode(&self, x: f64, y: f64) -> Result<u64, QuantizeError> {
+        if x.is_nan() || y.is_nan() {
+            return Err(QuantizeError::NotANumber);
+        }
+        if x < self.min_x || x > self.max_x || y < self.min_y || y > self.max_y {
+            return Err(QuantizeError::OutOfBounds { x, y });
+        }
+        let (x, y) = self.cell_of(x, y);
+        Ok(xy2h(x, y, self.order))
+    }
+
+    /// Convert a point to its hilbert index, while points outside of the bounding box are clamped to its border.
+    ///
+    /// # Errors
+    /// [`QuantizeError::NotANumber`] if a coordinate is NaN.
+    pub fn encode_clamped(&self, x: f64, y: f64) -> Result<u64, QuantizeError> {
+        if x.is_nan() || y.is_nan() {
+            return Err(QuantizeError::NotANumber);
+        }
+        let (x, y) = self.cell_of(x, y);
+        Ok(xy2h(x, y, self.order))
+    }
+
+    /// Convert a hilbert index to the centre of its cell.
+    ///
+    /// # Panics
+    /// If `h` exceeds the indices of the order.
+    #[must_use]
+    pub fn decode(&self, h: u64) -> (f64, f64) {
+        assert!(
+            h <= low_bits(u32::from(self.order) << 1),
+            "index exceeds the order of the quantizer"
+        );
+        let (x, y) = h2xy::<u32>(h, self.order);
+        let (min_x, min_y, max_x, max_y) = self.cell_bounds(x, y);
+        ((min_x + max_x) / 2.0, (min_y + max_y) / 2.0)
+    }
+
+    /// Grid coordinates of the cell which contains the point, which is clamped into the bounding box
+    pub(crate) fn cell_of(&self, x: f64, y: f64) -> (u32, u32) {
+        let x = x.clamp(self.min_x, self.max_x);
+        let y = y.clamp(self.min_y, self.max_y);
+        let max_cell = low_bits::<u32>(u32::from(self.order));
+        // The casts saturate and truncate towards zero, which is the floor for positive values
+        let x = (((x - self.min_x) / self.cell_width) as u32).min(max_cell);
+        let y = (((y - self.min_y) / self.cell_height) as u32).min(max_cell);
+        (x, y)
+    }
+
+    /// Bounds of the cell with the grid coordinates `(x, y)` as `(min_x, min_y, max_x, max_y)`
+    pub(crate) fn cell_bounds(&self, x: u32, y: u32) -> (f64, f64, f64, f64) {
+        let min_x = self.min_x + f64::from(x) * self.cell_width;
+        let min_y = self.min_y + f64::from(y) * self.cell_height;
+        (
+            min_x,
+            min_y,
+            min_x + self.cell_width,
+            min_y + self.cell_height,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quantize_and_rev() {
+        let quantizer = HilbertQuantizer::new(-10.0, 100.0, 30.0, 120.0, 3).unwrap();
+        assert_eq!(quantizer.cell_size(), (5.0, 2.5));
+        for h in 0..64 {
+            let (x, y) = quantizer.decode(h);
+            assert_eq!(quantizer.encode(x, y), Ok(h));
+            // The corners of the cell belong to the cell
+            let (width, height) = quantizer.cell_size();
+            assert_eq!(quantizer.encode(x - width / 2.0, y - height / 2.0), Ok(h));
+        }
+        assert_eq!(quantizer.decode(0), (-7.5, 101.25));
+        assert_eq!(quantizer.encode(-10.0, 100.0), Ok(0));
+        assert_eq!(quantizer.encode(30.0, 100.0), Ok(63));
+        assert_eq!(quantizer.encode(30.0, 120.0), Ok(xy2h(7u32, 7, 3)));
+    }
+
+    #[test]
+    fn quantize_full_order() {
+        let quantizer = HilbertQuantizer::new(0.0, 0.0, 1.0, 1.0, 32).unwrap();
+        assert_eq!(quantizer.encode(1.0, 0.0), Ok(xy2h(u32::MAX, 0, 32)));
+        assert_eq!(
+            quantizer.encode(0.5, 0.25),
+            Ok(xy2h(1u32 << 31, 1 << 30, 32))
+        );
+        let (x, y) = quantizer.decode(u64::MAX);
+        // The curve ends in the lower right corner
+        assert!(x < 1.0 && x > 0.99 && y > 0.0 && y < 0.01);
+
+        let single = HilbertQuantizer::new(0.0, 0.0, 1.0, 1.0, 0).unwrap();
+        assert_eq!(single.encode(0.7, 0.2), Ok(0));
+        assert_eq!(single.decode(0), (0.5, 0.5));
+    }
+
+    #[test]
+    fn quantize_errors() {
+        let quantizer = HilbertQuantizer::new(0.0, 0.0, 4.0, 4.0, 2).unwrap();
+        assert_eq!(
+            quantizer.encode(f64::NAN, 1.0),
+            Err(QuantizeError::NotANumber)
+        );
+        assert_eq!(
+            quantizer.encode_clamped(1.0, f64::NAN),
+            Err(QuantizeError::NotANumber)
+        );
+        assert_eq!(
+            quantizer.encode(-0.5, 1.0),
+            Err(QuantizeError::OutOfBounds { x: -0.5, y: 1.0 })
+        );
+        assert_eq!(
+            quantizer.encode(1.0, f64::INFINITY),
+            Err(QuantizeError::OutOfBounds {
+                x: 1.0,
+                y: f64::INFINITY
+            })
+        );
+        assert_eq!(
+            quantizer.encode_clamped(-0.5, 1.0),
+            quantizer.encode(0.0, 1.0)
+        );
+        assert_eq!(
+            quantizer.encode_clamped(9.0, f64::INFINITY),
+            quantizer.encode(4.0, 4.0)
+        );
+
+        assert_eq!(
+            HilbertQuantizer::new(0.0, 0.0, 1.0, 1.0, 33),
+            Err(QuantizeError::InvalidOrder { order: 33 })
+        );
+        assert!(matches!(
+            HilbertQuantizer::new(0.0, 0.0, 0.0, 1.0, 8),
+            Err(QuantizeError::InvalidBounds { .. })
+        ));
+        assert!(matches!(
+            HilbertQuantizer::new(0.0, f64::NAN, 1.0, 1.0, 8),
+            Err(QuantizeError::InvalidBounds { .. })
+        ));
+        assert!(matches!(
+            HilbertQuantizer::new(-f64::MAX, 0.0, f64::MAX, 1.0, 8),
+            Err(QuantizeError::InvalidBounds { .. })
+        ));
+        assert!(matches!(
+            HilbertQuantizer::new(0.0, -f64::MAX, 1.0, f64::MAX, 8),
+            Err(QuantizeError::InvalidBounds { .. })
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds the order")]
+    fn decode_beyond_order() {
+        let quantizer = HilbertQuantizer::new(0.0, 0.0, 1.0, 1.0, 2).unwrap();
+        let _ = quantizer.decode(16);
+    }
+}
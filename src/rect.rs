@@ -0,0 +1,659 @@
+use crate::{assert_order, low_bits, Unsigned, UnsignedBase, LUT_SH2SXY, MAX_LEVELS};
+use core::iter::FusedIterator;
+use core::ops::RangeInclusive;
+
+/// Axis aligned query rectangle with inclusive bounds
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Rect<T> {
+    pub(crate) x_min: T,
+    pub(crate) y_min: T,
+    pub(crate) x_max: T,
+    pub(crate) y_max: T,
+}
+
+/// How a node of the curve relates to a [`Rect`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Overlap {
+    Disjoint,
+    Partial,
+    Inside,
+}
+
+impl<T: Unsigned> Rect<T> {
+    /// Clip the rectangle to the grid of the given order. Returns `None` if nothing is left.
+    pub(crate) fn clip(x_min: T, y_min: T, x_max: T, y_max: T, order: u8) -> Option<Self> {
+        let grid_max: T = low_bits(order.into());
+        if x_min > x_max || y_min > y_max || x_min > grid_max || y_min > grid_max {
+            return None;
+        }
+        Some(Rect {
+            x_min,
+            y_min,
+            x_max: x_max.min(grid_max),
+            y_max: y_max.min(grid_max),
+        })
+    }
+
+    /// Relation of the square node with the lower left corner `(x, y)` and side length `2^level` to this rectangle
+    #[inline]
+    pub(crate) fn overlap(&self, x: T, y: T, level: u8) -> Overlap {
+        let mask: T = low_bits(level.into());
+        let (x_end, y_end) = (x | mask, y | mask);
+        if x_end < self.x_min || x > self.x_max || y_end < self.y_min || y > self.y_max {
+            Overlap::Disjoint
+        } else if self.x_min <= x && x_end <= self.x_max && self.y_min <= y && y_end <= self.y_max {
+            Overlap::Inside
+        } else {
+            Overlap::Partial
+        }
+    }
+
+    /// Extend the rectangle to contain the square node with the lower left corner `(x, y)` and side length
+    /// `2^level`
+    #[inline]
+    fn include(&mut self, x: T, y: T, level: u8) {
+        let mask: T = low_bits(level.into());
+        self.x_min = self.x_min.min(x);
+        self.y_min = self.y_min.min(y);
+        self.x_max = self.x_max.max(x | mask);
+        self.y_max = self.y_max.max(y | mask);
+    }
+
+    /// Number of cells with side length `2^level` which intersect the rectangle, saturated at `usize::MAX`
+    fn cells(&self, level: u8) -> usize {
+        let span = |min: T, max: T| {
+            ((max >> usize::from(level)) - (min >> usize::from(level)))
+                .try_into()
+                .map_or(usize::MAX, |n: usize| n.saturating_add(1))
+        };
+        span(self.x_min, self.x_max).saturating_mul(span(self.y_min, self.y_max))
+    }
+}
+
+/// Node of the curve whose children are visited by [`RectRanges`]
+#[derive(Clone, Copy, Debug)]
+struct Frame<T: Unsigned> {
+    h: T::Key,
+    x: T,
+    y: T,
+    state: u8,
+    // Next child to visit in curve order
+    digit: u8,
+}
+
+/// Iterator over the sorted hilbert index ranges which cover a rectangle.
+///
+/// Created by [`rect_to_ranges`] and [`rect_to_ranges_bounded`].
+#[derive(Clone, Debug)]
+pub struct RectRanges<T: Unsigned> {
+    rect: Rect<T>,
+    order: u8,
+    // Nodes at this level are emitted as a whole, even if they are only partially covered
+    stop_level: u8,
+    depth: usize,
+    frames: [Frame<T>; MAX_LEVELS],
+    pending: Option<(T::Key, T::Key)>,
+}
+
+impl<T: Unsigned> RectRanges<T> {
+    fn new(rect: Option<Rect<T>>, order: u8, max_ranges: Option<usize>) -> Self {
+        assert_order::<T>(order);
+        let frame = Frame {
+            h: T::Key::ZERO,
+            x: T::ZERO,
+            y: T::ZERO,
+            state: 0,
+            digit: 0,
+        };
+        let mut ranges = RectRanges {
+            rect: rect.unwrap_or(Rect {
+                x_min: T::ZERO,
+                y_min: T::ZERO,
+                x_max: T::ZERO,
+                y_max: T::ZERO,
+            }),
+            order,
+            stop_level: 0,
+            depth: 0,
+            frames: [frame; MAX_LEVELS],
+            pending: None,
+        };
+        let Some(rect) = rect else {
+            return ranges;
+        };
+        if let Some(max_ranges) = max_ranges {
+            let max_ranges = max_ranges.max(1);
+            ranges.stop_level = (0..order)
+                .find(|&level| rect.cells(level) <= max_ranges)
+                .unwrap_or(order);
+        }
+        if order == ranges.stop_level || rect.overlap(T::ZERO, T::ZERO, order) == Overlap::Inside {
+            ranges.pending = Some((T::Key::ZERO, low_bits(u32::from(order) << 1)));
+        } else {
+            ranges.depth = 1;
+        }
+        ranges
+    }
+
+    /// Next node which is emitted as a whole, in curve order
+    fn next_node(&mut self) -> Option<(T::Key, T::Key)> {
+        while self.depth > 0 {
+            let level = self.order - self.depth as u8;
+            let frame = &mut self.frames[self.depth - 1];
+            if frame.digit == 4 {
+                self.depth -= 1;
+                continue;
+            }
+            let digit = frame.digit;
+            frame.digit += 1;
+
+            let r = LUT_SH2SXY[usize::from(frame.state << 2 | digit)];
+            let x = frame.x | T::from(r >> 1 & 1) << usize::from(level);
+            let y = frame.y | T::from(r & 1) << usize::from(level);
+            let h = frame.h | T::Key::from(digit) << (usize::from(level) << 1);
+            let child = Frame {
+                h,
+                x,
+                y,
+                state: r >> 2,
+                digit: 0,
+            };
+
+            match self.rect.overlap(x, y, level) {
+                Overlap::Disjoint => {}
+                Overlap::Partial if level > self.stop_level => {
+                    self.frames[self.depth] = child;
+                    self.depth += 1;
+                }
+                Overlap::Partial | Overlap::Inside => {
+                    return Some((h, h | low_bits(u32::from(level) << 1)));
+                }
+            }
+        }
+        None
+    }
+}
+
+impl<T: Unsigned> Iterator for RectRanges<T> {
+    type Item = RangeInclusive<T::Key>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (start, mut end) = self.pending.take().or_else(|| self.next_node())?;
+        while let Some((next_start, next_end)) = self.next_node() {
+            // The next range starts after `end`, so it starts at least at one
+            if next_start - 1.into() == end {
+                end = next_end;
+            } else {
+                self.pending = Some((next_start, next_end));
+                break;
+            }
+        }
+        Some(start..=end)
+    }
+}
+
+impl<T: Unsigned> FusedIterator for RectRanges<T> {}
+
+/// Decompose the rectangle `[x_min, x_max] x [y_min, y_max]` into the hilbert index ranges which cover it exactly.
+///
+/// The ranges are sorted, merged and inclusive, so that the last index of a curve with the maximum order of `T`
+/// can be expressed. Parts of the rectangle outside of the curve are ignored.
+///
+/// The ranges are computed lazily by descending the same state machine which the LUT of [`xy2h`](crate::xy2h) encodes.
+///
+/// # Panics
+/// If `order` exceeds the number of bits of `T`.
+///
+/// # Examples
+///```
+/// let ranges: Vec<_> = fast_hilbert::rect_to_ranges(0u32, 0, 1, 3, 2).collect();
+/// assert_eq!(ranges, [0..=7]);
+///```
+pub fn rect_to_ranges<T: Unsigned>(
+    x_min: T,
+    y_min: T,
+    x_max: T,
+    y_max: T,
+    order: u8,
+) -> RectRanges<T> {
+    RectRanges::new(Rect::clip(x_min, y_min, x_max, y_max, order), order, None)
+}
+
+/// Same as [`rect_to_ranges`], but returns at most `max_ranges` ranges.
+///
+/// The ranges are an over-approximation of the rectangle. The curve is only refined down to the finest
+/// level at which the rectangle intersects at most `max_ranges` nodes, which are then covered completely.
+/// A `max_ranges` of zero is treated like one.
+///
+/// # Panics
+/// If `order` exceeds the number of bits of `T`.
+///
+/// # Examples
+///```
+/// let ranges: Vec<_> = fast_hilbert::rect_to_ranges(1u32, 0, 2, 1, 2).collect();
+/// assert_eq!(ranges, [1..=2, 13..=14]);
+/// let ranges: Vec<_> = fast_hilbert::rect_to_ranges_bounded(1u32, 0, 2, 1, 2, 1).collect();
+/// assert_eq!(ranges, [0..=15]);
+///```
+pub fn rect_to_ranges_bounded<T: Unsigned>(
+    x_min: T,
+    y_min: T,
+    x_max: T,
+    y_max: T,
+    order: u8,
+    max_ranges: usize,
+) -> RectRanges<T> {
+    RectRanges::new(
+        Rect::clip(x_min, y_min, x_max, y_max, order),
+        order,
+        Some(max_ranges),
+    )
+}
+
+/// Search direction of [`next_in_box`] and [`prev_in_box`]
+#[derive(Clone, Copy)]
+enum Direction {
+    Next,
+    Prev,
+}
+
+/// Find the closest index in `direction` of `h` within the node `h0` with the given `level`, `state` and
+/// lower left corner `(x, y)` whose cell lies in `rect`. The node must overlap the rectangle.
+fn search<T: Unsigned>(
+    rect: &Rect<T>,
+    h: T::Key,
+    (h0, x, y, state): (T::Key, T, T, u8),
+    level: u8,
+    direction: Direction,
+) -> Option<T::Key> {
+    if level == 0 {
+        return Some(h0);
+    }
+    let level = level - 1;
+    for i in 0..4 {
+        let digit = match direction {
+            Direction::Next => i,
+            Direction::Prev => 3 - i,
+        };
+        let r = LUT_SH2SXY[usize::from(state << 2 | digit)];
+        let child_x = x | T::from(r >> 1 & 1) << usize::from(level);
+        let child_y = y | T::from(r & 1) << usize::from(level);
+        let child_start = h0 | T::Key::from(digit) << (usize::from(level) << 1);
+        let child_end = child_start | low_bits(u32::from(level) << 1);
+
+        let (skip, before, after) = match direction {
+            Direction::Next => (child_end <= h, child_start > h, child_start),
+            Direction::Prev => (child_start >= h, child_end < h, child_end),
+        };
+        if skip {
+            continue;
+        }
+        match rect.overlap(child_x, child_y, level) {
+            Overlap::Disjoint => {}
+            // The whole child lies behind `h`, so its first cell in search direction is the result
+            Overlap::Inside if before => return Some(after),
+            Overlap::Inside | Overlap::Partial => {
+                let child = (child_start, child_x, child_y, r >> 2);
+                if let Some(found) = search(rect, h, child, level, direction) {
+                    return Some(found);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Smallest hilbert index greater than `h` whose cell lies in the rectangle `[x_min, x_max] x [y_min, y_max]`.
+///
+/// This is the hilbert curve equivalent of the `BIGMIN` computation of the Z-order curve. It allows a cursor over
+/// a table sorted by hilbert index to skip directly from a key outside of the query rectangle to the next key inside.
+/// Returns `None` if there is no such index.
+///
+/// # Panics
+/// If `order` exceeds the number of bits of `T`.
+///
+/// # Examples
+///```
+/// // The cells of the rectangle have the indices 1, 2, 13 and 14
+/// assert_eq!(fast_hilbert::next_in_box(2u64, 1u32, 0, 2, 1, 2), Some(13));
+/// assert_eq!(fast_hilbert::next_in_box(14u64, 1u32, 0, 2, 1, 2), None);
+///```
+pub fn next_in_box<T: Unsigned>(
+    h: T::Key,
+    x_min: T,
+    y_min: T,
+    x_max: T,
+    y_max: T,
+    order: u8,
+) -> Option<T::Key> {
+    assert_order::<T>(order);
+    let rect = Rect::clip(x_min, y_min, x_max, y_max, order)?;
+    if low_bits::<T::Key>(u32::from(order) << 1) <= h {
+        return None;
+    }
+    let root = (T::Key::ZERO, T::ZERO, T::ZERO, 0);
+    search(&rect, h, root, order, Direction::Next)
+}
+
+/// Largest hilbert index less than `h` whose cell lies in the rectangle `[x_min, x_max] x [y_min, y_max]`.
+///
+/// Counterpart of [`next_in_box`] for cursors which move backwards. Returns `None` if there is no such index.
+///
+/// # Panics
+/// If `order` exceeds the number of bits of `T`.
+///
+/// # Examples
+///```
+/// // The cells of the rectangle have the indices 1, 2, 13 and 14
+/// assert_eq!(fast_hilbert::prev_in_box(13u64, 1u32, 0, 2, 1, 2), Some(2));
+/// assert_eq!(fast_hilbert::prev_in_box(1u64, 1u32, 0, 2, 1, 2), None);
+///```
+pub fn prev_in_box<T: Unsigned>(
+    h: T::Key,
+    x_min: T,
+    y_min: T,
+    x_max: T,
+    y_max: T,
+    order: u8,
+) -> Option<T::Key> {
+    assert_order::<T>(order);
+    let rect = Rect::clip(x_min, y_min, x_max, y_max, order)?;
+    if h == T::Key::ZERO {
+        return None;
+    }
+    let root = (T::Key::ZERO, T::ZERO, T::ZERO, 0);
+    search(&rect, h, root, order, Direction::Prev)
+}
+
+/// Lower left corner and state of the child `digit` of the node with the lower left corner `(x, y)` and `state`,
+/// whose children have the side length `2^level`
+#[inline]
+fn child<T: Unsigned>(x: T, y: T, state: u8, digit: u8, level: u8) -> (T, T, u8) {
+    let r = LUT_SH2SXY[usize::from(state << 2 | digit)];
+    let x = x | T::from(r >> 1 & 1) << usize::from(level);
+    let y = y | T::from(r & 1) << usize::from(level);
+    (x, y, r >> 2)
+}
+
+/// Extend `bbox` by the cells of the node `(x, y, state)` with side length `2^level` which follow `h` in
+/// `direction`, including the cell of `h` itself.
+fn include_edge<T: Unsigned>(
+    bbox: &mut Rect<T>,
+    h: T::Key,
+    (mut x, mut y, mut state): (T, T, u8),
+    level: u8,
+    direction: Direction,
+) {
+    for level in (0..level).rev() {
+        let h_digit = (h >> (usize::from(level) << 1)).as_u8() & 0b11;
+        let siblings = match direction {
+            Direction::Next => h_digit + 1..4,
+            Direction::Prev => 0..h_digit,
+        };
+        for digit in siblings {
+            let (x, y, _) = child(x, y, state, digit, level);
+            bbox.include(x, y, level);
+        }
+        (x, y, state) = child(x, y, state, h_digit, level);
+    }
+    bbox.include(x, y, 0);
+}
+
+/// Tight bounding box `(x_min, y_min, x_max, y_max)` of the cells of the hilbert index range `[a, b)`.
+///
+/// The bounding box is computed by descending the state machine along the first and the last index of the range.
+/// Nodes in between are covered as a whole, so the runtime is O(order) instead of the length of the range.
+///
+/// The exclusive end `b` cannot express the last index of a curve with the maximum order of `T`, use
+/// [`range_bbox_inclusive`] for such ranges.
+///
+/// # Panics
+/// If `order` exceeds the number of bits of `T`, if the range is empty or if `b` exceeds the indices of `order`.
+///
+/// # Examples
+///```
+/// // The cells 1 and 2 are the right column of the lower left quadrant
+/// assert_eq!(fast_hilbert::range_bbox(1u64, 3, 2), (1u32, 0, 1, 1));
+/// assert_eq!(fast_hilbert::range_bbox(0u64, 16, 2), (0u32, 0, 3, 3));
+///```
+pub fn range_bbox<T: Unsigned>(a: T::Key, b: T::Key, order: u8) -> (T, T, T, T) {
+    assert!(a < b, "range {a:?}..{b:?} is empty");
+    range_bbox_inclusive(a..=b - T::Key::from(1), order)
+}
+
+/// Tight bounding box `(x_min, y_min, x_max, y_max)` of the cells of the inclusive hilbert index range.
+///
+/// Same as [`range_bbox`], but takes the ranges of [`rect_to_ranges`], which can end with the last index of a
+/// curve with the maximum order of `T`.
+///
+/// # Panics
+/// If `order` exceeds the number of bits of `T`, if the range is empty or if its end exceeds the indices of
+/// `order`.
+///
+/// # Examples
+///```
+/// assert_eq!(fast_hilbert::range_bbox_inclusive(1u64..=2, 2), (1u32, 0, 1, 1));
+/// assert_eq!(fast_hilbert::range_bbox_inclusive(0..=u64::MAX, 32), (0, 0, u32::MAX, u32::MAX));
+///```
+pub fn range_bbox_inclusive<T: Unsigned>(range: RangeInclusive<T::Key>, order: u8) -> (T, T, T, T) {
+    assert_order::<T>(order);
+    let (a, last) = range.into_inner();
+    assert!(a <= last, "range {a:?}..={last:?} is empty");
+    assert!(
+        last <= low_bits(u32::from(order) << 1),
+        "range {a:?}..={last:?} exceeds the indices of order {order}"
+    );
+
+    // Descend along the common prefix of both ends
+    let (mut x, mut y, mut state) = (T::ZERO, T::ZERO, 0);
+    let mut level = order;
+    let (first_digit, last_digit) = loop {
+        if level == 0 {
+            return (x, y, x, y);
+        }
+        level -= 1;
+        let shift = usize::from(level) << 1;
+        let first_digit = (a >> shift).as_u8() & 0b11;
+        let last_digit = (last >> shift).as_u8() & 0b11;
+        if first_digit != last_digit {
+            break (first_digit, last_digit);
+        }
+        (x, y, state) = child(x, y, state, first_digit, level);
+    };
+
+    let mut bbox = Rect {
+        x_min: !T::ZERO,
+        y_min: !T::ZERO,
+        x_max: T::ZERO,
+        y_max: T::ZERO,
+    };
+    for digit in first_digit + 1..last_digit {
+        let (x, y, _) = child(x, y, state, digit, level);
+        bbox.include(x, y, level);
+    }
+    let first = child(x, y, state, first_digit, level);
+    include_edge(&mut bbox, a, first, level, Direction::Next);
+    let last_node = child(x, y, state, last_digit, level);
+    include_edge(&mut bbox, last, last_node, level, Direction::Prev);
+    (bbox.x_min, bbox.y_min, bbox.x_max, bbox.y_max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{h2xy, xy2h};
+
+    fn brute_force(x_min: u32, y_min: u32, x_max: u32, y_max: u32, order: u8) -> Vec<u64> {
+        let mut h: Vec<u64> = (x_min..=x_max)
+            .flat_map(|x| (y_min..=y_max).map(move |y| xy2h(x, y, order)))
+            .collect();
+        h.sort_unstable();
+        h
+    }
+
+    fn expand(ranges: &[RangeInclusive<u64>]) -> Vec<u64> {
+        ranges.iter().flat_map(Clone::clone).collect()
+    }
+
+    #[test]
+    fn ranges_match_brute_force() {
+        for order in 1..=4u8 {
+            let max = (1u32 << order) - 1;
+            for x_min in 0..=max {
+                for x_max in x_min..=max {
+                    for y_min in 0..=max {
+                        for y_max in y_min..=max {
+                            let ranges: Vec<_> =
+                                rect_to_ranges(x_min, y_min, x_max, y_max, order).collect();
+                            assert_eq!(
+                                expand(&ranges),
+                                brute_force(x_min, y_min, x_max, y_max, order)
+                            );
+                            for pair in ranges.windows(2) {
+                                assert!(pair[0].end() + 1 < *pair[1].start(), "{ranges:?}");
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn bounded_ranges_cover_rect() {
+        let order = 6;
+        for (x_min, y_min, x_max, y_max) in [
+            (3, 5, 40, 17),
+            (0, 0, 63, 63),
+            (10, 10, 10, 10),
+            (1, 2, 62, 61),
+        ] {
+            let exact = brute_force(x_min, y_min, x_max, y_max, order);
+            let exact_count = rect_to_ranges(x_min, y_min, x_max, y_max, order).count();
+            for max_ranges in [0, 1, 2, 3, 5, 10, 100, 10000] {
+                let ranges: Vec<_> =
+                    rect_to_ranges_bounded(x_min, y_min, x_max, y_max, order, max_ranges).collect();
+                assert!(ranges.len() <= max_ranges.max(1));
+                assert!(ranges
+                    .windows(2)
+                    .all(|pair| pair[0].end() < pair[1].start()));
+                let covered = expand(&ranges);
+                assert!(exact.iter().all(|h| covered.binary_search(h).is_ok()));
+                if max_ranges >= 10000 {
+                    assert_eq!(ranges.len(), exact_count);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn ranges_outside_of_grid() {
+        assert_eq!(rect_to_ranges(4u32, 0, 9, 9, 2).next(), None);
+        assert_eq!(rect_to_ranges(3u32, 2, 1, 3, 2).next(), None);
+        let clipped: Vec<_> = rect_to_ranges(2u32, 0, 9, 9, 2).collect();
+        assert_eq!(clipped, [8..=15]);
+    }
+
+    #[test]
+    fn next_and_prev_match_brute_force() {
+        for order in 1..=4u8 {
+            let max = (1u32 << order) - 1;
+            let end = 1u64 << (2 * order);
+            for (x_min, y_min, x_max, y_max) in [
+                (0, 0, max, max),
+                (0, 0, 0, 0),
+                (max, 0, max, 0),
+                (1, 0, max / 2 + 1, max),
+                (max / 3, max / 2, max - max / 4, max),
+                (max / 2, max / 2, max / 2, max / 2 + 1),
+            ] {
+                let inside = brute_force(x_min, y_min, x_max, y_max, order);
+                for h in 0..end + 2 {
+                    let next = inside.iter().copied().find(|&i| i > h);
+                    let prev = inside.iter().copied().rev().find(|&i| i < h);
+                    assert_eq!(next_in_box(h, x_min, y_min, x_max, y_max, order), next);
+                    assert_eq!(prev_in_box(h, x_min, y_min, x_max, y_max, order), prev);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn next_and_prev_full_order() {
+        let (min, max) = (u64::MAX - 1, u64::MAX);
+        let first = xy2h(max, 0, 64).min(xy2h(min, 0, 64));
+        assert_eq!(next_in_box(0u128, min, 0, max, 0, 64), Some(first));
+        assert_eq!(next_in_box(u128::MAX, 0, 0, max, max, 64), None);
+        assert_eq!(
+            prev_in_box(u128::MAX, 0, 0, max, max, 64),
+            Some(u128::MAX - 1)
+        );
+        assert_eq!(prev_in_box(0u128, 0, 0, max, max, 64), None);
+    }
+
+    #[test]
+    fn ranges_full_order() {
+        let all: Vec<_> = rect_to_ranges(0u64, 0, u64::MAX, u64::MAX, 64).collect();
+        assert_eq!(all, [0..=u128::MAX]);
+        let last: Vec<_> = rect_to_ranges(u8::MAX, 0, u8::MAX, 0, 8).collect();
+        assert_eq!(last, [u16::MAX..=u16::MAX]);
+        let half: Vec<_> = rect_to_ranges(0u8, 0, 127, 255, 8).collect();
+        assert_eq!(half, [0..=0x7FFF]);
+    }
+
+    #[test]
+    fn range_bbox_matches_brute_force() {
+        for order in 0..=8u8 {
+            let cells = 1u32 << (2 * order);
+            // All ranges for small orders and samples of the ranges of a few starts for the larger ones
+            let step = if order <= 4 { 1 } else { 997 };
+            for a in (0..cells).step_by(step) {
+                let (x, y) = h2xy::<u8>(a as u16, order);
+                let mut expected = (x, y, x, y);
+                for b in a + 1..=cells {
+                    let (x, y) = h2xy::<u8>((b - 1) as u16, order);
+                    expected = (
+                        expected.0.min(x),
+                        expected.1.min(y),
+                        expected.2.max(x),
+                        expected.3.max(y),
+                    );
+                    let sampled = order <= 4 || b - a <= 64 || (b - a) % 127 == 0;
+                    if sampled {
+                        let range = a as u16..=(b - 1) as u16;
+                        assert_eq!(range_bbox_inclusive(range, order), expected);
+                        // The end of the full range at order 8 does not fit into the key type
+                        if let Ok(b) = u16::try_from(b) {
+                            assert_eq!(range_bbox(a as u16, b, order), expected);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn range_bbox_full_order() {
+        let last = xy2h(u32::MAX, 0, 32);
+        assert_eq!(range_bbox(0u64, last, 32), (0, 0, u32::MAX, u32::MAX));
+        let (x, y) = h2xy::<u32>(last - 1, 32);
+        assert_eq!(range_bbox(last - 1, last, 32), (x, y, x, y));
+        assert_eq!(
+            range_bbox(1u64 << 62, 3 << 62, 32),
+            (0, 1 << 31, u32::MAX, u32::MAX)
+        );
+        // The ranges of a rectangle at the border of the curve end with the last index
+        let ranges: Vec<_> = rect_to_ranges(u32::MAX - 1, 0, u32::MAX, 1, 32).collect();
+        assert_eq!(*ranges.last().unwrap().end(), u64::MAX);
+        for range in ranges {
+            let (x_min, y_min, x_max, y_max) = range_bbox_inclusive::<u32>(range, 32);
+            assert!(x_min >= u32::MAX - 1 && y_min == 0 && x_max == u32::MAX && y_max <= 1);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "is empty")]
+    fn range_bbox_empty() {
+        let _: (u32, u32, u32, u32) = range_bbox(5u64, 5, 4);
+    }
+}
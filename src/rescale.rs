@@ -0,0 +1,112 @@
+use crate::{assert_order, Unsigned, UnsignedBase, LUT_SH2SXY};
+
+/// Convert the hilbert index `h` of a curve with `from_order` to the index of the same location on a curve with
+/// `to_order`, without decoding the coordinates.
+///
+/// A coarser order truncates the key to the cell which contains the location. A finer order extends the key with
+/// the cell in the lower left corner of the location, which is the result of shifting the decoded coordinates
+/// to the left. Both orders are taken into account, so the flip of the first step between odd and even orders is
+/// handled.
+///
+/// # Arguments
+/// * `h` - Hilbert index of a curve with `from_order`
+/// * `from_order` - The hilbert curve order of `h`
+/// * `to_order` - The hilbert curve order of the result
+///
+/// # Panics
+/// If an order exceeds the bits of `T`.
+///
+/// # Examples
+///```
+/// use fast_hilbert::{rescale_h, xy2h};
+///
+/// let h = xy2h(5u32, 6, 3);
+/// assert_eq!(rescale_h::<u32>(h, 3, 2), xy2h(2u32, 3, 2));
+/// assert_eq!(rescale_h::<u32>(h, 3, 6), xy2h(40u32, 48, 6));
+///```
+pub fn rescale_h<T: Unsigned>(h: T::Key, from_order: u8, to_order: u8) -> T::Key {
+    assert_order::<T>(from_order);
+    assert_order::<T>(to_order);
+    if to_order <= from_order {
+        if to_order == 0 {
+            return T::Key::ZERO;
+        }
+        return h >> (usize::from(from_order - to_order) << 1);
+    }
+
+    if from_order == 0 {
+        // The lower left corner of the root cell is the start of the curve
+        return T::Key::ZERO;
+    }
+    let mut state = 0u8;
+    for level in (0..usize::from(from_order)).rev() {
+        let digit = (h >> (level << 1)).as_u8() & 0b11;
+        state = LUT_SH2SXY[usize::from(state << 2 | digit)] >> 2;
+    }
+    // States 0 and 1 enter the lower left quadrant with digit 0 and alternate between each other. States 2 and 3
+    // reach it with digit 2 and keep their state, which results in the repeated digits `0b1010...`.
+    let digit = T::Key::from(if state < 2 { 0 } else { 2 });
+    (from_order..to_order).fold(h, |h, _| h << 2usize | digit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{h2xy, low_bits, xy2h};
+
+    fn decode_shift_encode(h: u16, from_order: u8, to_order: u8) -> u16 {
+        let (x, y) = h2xy::<u8>(h, from_order);
+        // Shifts by the full width of the type from and to the root cell at order zero result in zero
+        let (x, y) = if to_order < from_order {
+            let shift = u32::from(from_order - to_order);
+            (
+                x.checked_shr(shift).unwrap_or(0),
+                y.checked_shr(shift).unwrap_or(0),
+            )
+        } else {
+            let shift = u32::from(to_order - from_order);
+            (
+                x.checked_shl(shift).unwrap_or(0),
+                y.checked_shl(shift).unwrap_or(0),
+            )
+        };
+        xy2h(x, y, to_order)
+    }
+
+    #[test]
+    fn rescale_like_decode_shift_encode() {
+        for from_order in 0..=8 {
+            for to_order in 0..=8 {
+                for h in 0..=low_bits::<u16>(u32::from(from_order) << 1) {
+                    assert_eq!(
+                        rescale_h::<u8>(h, from_order, to_order),
+                        decode_shift_encode(h, from_order, to_order),
+                        "h {h} from {from_order} to {to_order}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rescale_full_order() {
+        let (x, y) = (0xDEAD_BEEFu32, 0x1234_5678);
+        let h = xy2h(x, y, 32);
+        assert_eq!(rescale_h::<u32>(h, 32, 0), 0);
+        assert_eq!(rescale_h::<u32>(h, 32, 32), h);
+        assert_eq!(rescale_h::<u32>(h, 32, 7), xy2h(x >> 25, y >> 25, 7));
+        let coarse = xy2h(x >> 31, y >> 31, 1);
+        assert_eq!(rescale_h::<u32>(coarse, 1, 32), xy2h(1u32 << 31, 0, 32));
+        assert_eq!(rescale_h::<u32>(0, 0, 32), 0);
+        // Round trip from a coarse to a fine order and back
+        for h in 0..1u64 << 10 {
+            assert_eq!(rescale_h::<u32>(rescale_h::<u32>(h, 5, 31), 31, 5), h);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds the number of bits of the coordinate type")]
+    fn rescale_beyond_type() {
+        let _ = rescale_h::<u8>(0, 8, 9);
+    }
+}
@@ -0,0 +1,135 @@
+use crate::{h2xy, max_order, xy2h, Unsigned};
+
+/// Signed integer coordinate type which is mapped onto the hilbert curve of its unsigned counterpart.
+///
+/// Flipping the sign bit maps `MIN..=MAX` monotonically onto `0..=MAX` of the unsigned type, so neighbouring
+/// coordinates stay neighbours, including `-1` and `0`.
+pub trait Signed: Copy {
+    type Coord: Unsigned; // Unsigned type of the same width
+
+    /// Map onto the unsigned type while preserving the order
+    fn to_unsigned(self) -> Self::Coord;
+
+    /// Inverse of [`Signed::to_unsigned`]
+    fn from_unsigned(coord: Self::Coord) -> Self;
+}
+
+macro_rules! signed_impl {
+    ($T:ty, $U:ty) => {
+        impl Signed for $T {
+            type Coord = $U;
+
+            #[inline]
+            fn to_unsigned(self) -> $U {
+                (self as $U) ^ (1 << (<$U>::BITS - 1))
+            }
+
+            #[inline]
+            fn from_unsigned(coord: $U) -> Self {
+                (coord ^ (1 << (<$U>::BITS - 1))) as $T
+            }
+        }
+    };
+}
+
+signed_impl!(i64, u64);
+signed_impl!(i32, u32);
+signed_impl!(i16, u16);
+signed_impl!(i8, u8);
+
+/// Convert signed coordinates from 2D to 1D hilbert space.
+///
+/// The whole signed range is covered by the curve with the order of the full type width.
+/// The origin is located in the center of the curve.
+///
+/// # Arguments
+/// * `x` - Coordinate in 2D space
+/// * `y` - Coordinate in 2D space
+///
+/// # Examples
+///```
+/// let h = fast_hilbert::xy2h_signed(-1i32, 0);
+/// assert_eq!(fast_hilbert::h2xy_signed::<i32>(h), (-1, 0));
+/// assert_eq!(h, fast_hilbert::xy2h(i32::MAX as u32, 1 << 31, 32));
+///```
+pub fn xy2h_signed<T: Signed>(x: T, y: T) -> <T::Coord as Unsigned>::Key {
+    xy2h(x.to_unsigned(), y.to_unsigned(), max_order::<T::Coord>())
+}
+
+/// Convert from 1D hilbert space to signed 2D coordinates.
+///
+/// Inverse of [`xy2h_signed`].
+///
+/// # Arguments
+/// * `h` - Coordinate in 1D hilbert space
+///
+/// # Examples
+///```
+/// let (x, y) = fast_hilbert::h2xy_signed::<i16>(0);
+/// assert_eq!((x, y), (i16::MIN, i16::MIN));
+///```
+pub fn h2xy_signed<T: Signed>(h: <T::Coord as Unsigned>::Key) -> (T, T) {
+    let (x, y) = h2xy(h, max_order::<T::Coord>());
+    (T::from_unsigned(x), T::from_unsigned(y))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_type<T>(values: &[T])
+    where
+        T: Signed + PartialEq + core::fmt::Debug,
+        T::Coord: PartialOrd,
+    {
+        for &x in values {
+            for &y in values {
+                let h = xy2h_signed(x, y);
+                assert_eq!(h2xy_signed::<T>(h), (x, y));
+            }
+        }
+        for pair in values.windows(2) {
+            assert!(pair[0].to_unsigned() < pair[1].to_unsigned());
+        }
+    }
+
+    #[test]
+    fn signed_min_max() {
+        check_type(&[i8::MIN, i8::MIN + 1, -1, 0, 1, i8::MAX - 1, i8::MAX]);
+        check_type(&[i16::MIN, -300, -1, 0, 1, 300, i16::MAX]);
+        check_type(&[i32::MIN, i32::MIN + 1, -70000, -1, 0, 1, 70000, i32::MAX]);
+        check_type(&[
+            i64::MIN,
+            -(1 << 40),
+            -1,
+            0,
+            1,
+            1 << 40,
+            i64::MAX - 1,
+            i64::MAX,
+        ]);
+        assert_eq!(h2xy_signed::<i8>(0), (i8::MIN, i8::MIN));
+        assert_eq!(h2xy_signed::<i8>(u16::MAX), (i8::MAX, i8::MIN));
+    }
+
+    #[test]
+    fn signed_all_i8() {
+        let mut seen = vec![false; 1 << 16];
+        for x in i8::MIN..=i8::MAX {
+            for y in i8::MIN..=i8::MAX {
+                let h = xy2h_signed(x, y);
+                assert!(!core::mem::replace(&mut seen[usize::from(h)], true));
+                assert_eq!(h2xy_signed::<i8>(h), (x, y));
+            }
+        }
+    }
+
+    #[test]
+    fn signed_adjacent_across_zero() {
+        for h in 0..u16::MAX {
+            let (ax, ay) = h2xy_signed::<i8>(h);
+            let (bx, by) = h2xy_signed::<i8>(h + 1);
+            assert_eq!(ax.abs_diff(bx) + ay.abs_diff(by), 1);
+        }
+    }
+}
@@ -0,0 +1,182 @@
+//! Bulk encoding which processes several coordinate pairs at once using `core::arch` intrinsics.
+//!
+//! This is the only module of the crate which contains `unsafe` code. It is only compiled with the opt-in `simd` feature.
+#![allow(unsafe_code)]
+
+use crate::{xy2h_lowest, xy2h_slice_soa, xy_lowest_order};
+
+/// Convert many `(x, y)` pairs from 2D to 1D hilbert space at once.
+///
+/// On `x86_64` CPUs with AVX2 support, eight coordinate pairs are encoded in parallel by gathering
+/// from the `LUT_3` lookup table. The CPU features are detected at runtime. On all other targets
+/// this falls back to [`xy2h_slice_soa`]. The results are identical to calling [`xy2h`](crate::xy2h)
+/// for every point.
+///
+/// # Panics
+/// If `x`, `y` and `out` differ in length.
+///
+/// # Examples
+///```
+/// let mut h = [0u64; 2];
+/// fast_hilbert::xy2h_bulk(&[1, 1], &[0, 1], 1, &mut h);
+/// assert_eq!(h, [3, 2]);
+///```
+pub fn xy2h_bulk(x: &[u32], y: &[u32], order: u8, out: &mut [u64]) {
+    #[cfg(target_arch = "x86_64")]
+    if std::is_x86_feature_detected!("avx2") {
+        assert_eq!(x.len(), y.len(), "x and y must have the same length");
+        assert_eq!(
+            x.len(),
+            out.len(),
+            "input and output must have the same length"
+        );
+        let bits = x.iter().chain(y).fold(0, |acc, &v| acc | v);
+        let lowest_order = xy_lowest_order(bits, order);
+        // SAFETY: AVX2 support was checked at runtime
+        unsafe { avx2::xy2h(x, y, lowest_order, out) };
+        return;
+    }
+    xy2h_slice_soa(x, y, order, out);
+}
+
+#[cfg(target_arch = "x86_64")]
+mod avx2 {
+    use super::xy2h_lowest;
+    use crate::LUT_3;
+    use core::arch::x86_64::{
+        __m256i, _mm256_and_si256, _mm256_i32gather_epi32, _mm256_loadu_si256, _mm256_or_si256,
+        _mm256_set1_epi32, _mm256_setzero_si256, _mm256_sll_epi32, _mm256_slli_epi32,
+        _mm256_srl_epi32, _mm256_storeu_si256, _mm_cvtsi32_si128,
+    };
+
+    const LANES: usize = 8;
+
+    // `LUT_3` widened to 32 bit entries, because the gather instruction always loads 32 bits
+    static LUT_3_WIDE: [i32; 256] = {
+        let mut lut = [0; 256];
+        let mut i = 0;
+        while i < lut.len() {
+            lut[i] = LUT_3[i] as i32;
+            i += 1;
+        }
+        lut
+    };
+
+    /// Same state machine as [`xy2h_lowest`], but for eight lanes at once.
+    ///
+    /// The 64 bit hilbert indices are accumulated in two vectors which hold the lower and upper 32 bits.
+    // Loads and stores are unaligned, so the pointer casts are fine
+    #[allow(clippy::cast_ptr_alignment)]
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn xy2h(x: &[u32], y: &[u32], lowest_order: u8, out: &mut [u64]) {
+        let seven = _mm256_set1_epi32(7);
+        let state_mask = _mm256_set1_epi32(0b1100_0000);
+        let hhh_mask = _mm256_set1_epi32(63);
+
+        let x_chunks = x.chunks_exact(LANES);
+        let y_chunks = y.chunks_exact(LANES);
+        let mut out_chunks = out.chunks_exact_mut(LANES);
+        for ((x, y), out) in x_chunks.zip(y_chunks).zip(&mut out_chunks) {
+            let x = _mm256_loadu_si256(x.as_ptr().cast::<__m256i>());
+            let y = _mm256_loadu_si256(y.as_ptr().cast::<__m256i>());
+
+            let mut state = _mm256_setzero_si256();
+            let mut low = _mm256_setzero_si256();
+            let mut high = _mm256_setzero_si256();
+            let mut shift_factor = i32::from(lowest_order) - 3;
+
+            while shift_factor > 0 {
+                let shift = _mm_cvtsi32_si128(shift_factor);
+                let x_in =
+                    _mm256_slli_epi32::<3>(_mm256_and_si256(_mm256_srl_epi32(x, shift), seven));
+                let y_in = _mm256_and_si256(_mm256_srl_epi32(y, shift), seven);
+                let index = _mm256_or_si256(_mm256_or_si256(x_in, y_in), state);
+
+                let r = _mm256_i32gather_epi32::<4>(LUT_3_WIDE.as_ptr(), index);
+                state = _mm256_and_si256(r, state_mask);
+                let hhh = _mm256_and_si256(r, hhh_mask);
+
+                // Shift counts above 31 clear all bits, so each part only receives the bits which belong to it
+                let h_shift = shift_factor << 1;
+                low = _mm256_or_si256(low, _mm256_sll_epi32(hhh, _mm_cvtsi32_si128(h_shift)));
+                let high_part = if h_shift >= 32 {
+                    _mm256_sll_epi32(hhh, _mm_cvtsi32_si128(h_shift - 32))
+                } else {
+                    _mm256_srl_epi32(hhh, _mm_cvtsi32_si128(32 - h_shift))
+                };
+                high = _mm256_or_si256(high, high_part);
+                shift_factor -= 3;
+            }
+
+            let shift = _mm_cvtsi32_si128(-shift_factor);
+            let x_in = _mm256_slli_epi32::<3>(_mm256_and_si256(_mm256_sll_epi32(x, shift), seven));
+            let y_in = _mm256_and_si256(_mm256_sll_epi32(y, shift), seven);
+            let index = _mm256_or_si256(_mm256_or_si256(x_in, y_in), state);
+            let r = _mm256_i32gather_epi32::<4>(LUT_3_WIDE.as_ptr(), index);
+            let hhh = _mm256_and_si256(r, hhh_mask);
+            low = _mm256_or_si256(
+                low,
+                _mm256_srl_epi32(hhh, _mm_cvtsi32_si128(-shift_factor << 1)),
+            );
+
+            let mut low_lanes = [0u32; LANES];
+            let mut high_lanes = [0u32; LANES];
+            _mm256_storeu_si256(low_lanes.as_mut_ptr().cast::<__m256i>(), low);
+            _mm256_storeu_si256(high_lanes.as_mut_ptr().cast::<__m256i>(), high);
+            for ((h, low), high) in out.iter_mut().zip(low_lanes).zip(high_lanes) {
+                *h = u64::from(high) << 32 | u64::from(low);
+            }
+        }
+
+        let rest = x.len() - x.len() % LANES;
+        for ((&x, &y), h) in x[rest..]
+            .iter()
+            .zip(&y[rest..])
+            .zip(out_chunks.into_remainder())
+        {
+            *h = xy2h_lowest(x, y, lowest_order);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{xorshift, xy2h};
+
+    #[test]
+    fn bulk_matches_scalar() {
+        let mut next = xorshift(0x9e37_79b9_7f4a_7c15);
+        for order in [1, 2, 3, 8, 16, 31, 32] {
+            for len in [0, 1, 7, 8, 9, 64, 1001] {
+                let mask = ((1u64 << order) - 1) as u32;
+                let x: Vec<u32> = (0..len).map(|_| next() as u32 & mask).collect();
+                let y: Vec<u32> = (0..len).map(|_| next() as u32 & mask).collect();
+                let mut h = vec![0; len];
+                xy2h_bulk(&x, &y, order, &mut h);
+                for i in 0..len {
+                    assert_eq!(
+                        h[i],
+                        xy2h(x[i], y[i], order),
+                        "order {order} x {} y {}",
+                        x[i],
+                        y[i]
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn bulk_extreme_values() {
+        let x = [u32::MAX, 0, u32::MAX, 0, 1, u32::MAX - 1, 12345, 1 << 31, 7];
+        let y = [u32::MAX, u32::MAX, 0, 0, 2, u32::MAX - 2, 54321, 1 << 30, 9];
+        for order in [31, 32] {
+            let mut h = [0; 9];
+            xy2h_bulk(&x, &y, order, &mut h);
+            for i in 0..x.len() {
+                assert_eq!(h[i], xy2h(x[i], y[i], order));
+            }
+        }
+    }
+}
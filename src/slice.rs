@@ -0,0 +1,175 @@
+use crate::{h2xy_lowest, h_lowest_order, xy2h_lowest, xy_lowest_order, Unsigned, UnsignedBase};
+
+/// Batch version of [`xy2h`](crate::xy2h) for a slice of `(x, y)` pairs.
+///
+/// The lowest order is derived once for the whole slice instead of once per point, so all points
+/// share the same number of LUT iterations. The results are identical to calling `xy2h` for every point.
+///
+/// # Panics
+/// If `xy` and `out` differ in length.
+///
+/// # Examples
+///```
+/// let mut h = [0u64; 2];
+/// fast_hilbert::xy2h_slice(&[(1u32, 0), (1, 1)], 1, &mut h);
+/// assert_eq!(h, [3, 2]);
+///```
+pub fn xy2h_slice<T: Unsigned>(xy: &[(T, T)], order: u8, out: &mut [T::Key]) {
+    assert_eq!(
+        xy.len(),
+        out.len(),
+        "input and output must have the same length"
+    );
+    let bits = xy.iter().fold(T::ZERO, |acc, &(x, y)| acc | x | y);
+    let lowest_order = xy_lowest_order(bits, order);
+    for (&(x, y), h) in xy.iter().zip(out) {
+        *h = xy2h_lowest(x, y, lowest_order);
+    }
+}
+
+/// Batch version of [`xy2h`](crate::xy2h) with the coordinates stored in two separate slices.
+///
+/// # Panics
+/// If `x`, `y` and `out` differ in length.
+///
+/// # Examples
+///```
+/// let mut h = [0u64; 2];
+/// fast_hilbert::xy2h_slice_soa(&[1u32, 1], &[0, 1], 1, &mut h);
+/// assert_eq!(h, [3, 2]);
+///```
+pub fn xy2h_slice_soa<T: Unsigned>(x: &[T], y: &[T], order: u8, out: &mut [T::Key]) {
+    assert_eq!(x.len(), y.len(), "x and y must have the same length");
+    assert_eq!(
+        x.len(),
+        out.len(),
+        "input and output must have the same length"
+    );
+    let bits = x.iter().chain(y).fold(T::ZERO, |acc, &v| acc | v);
+    let lowest_order = xy_lowest_order(bits, order);
+    for ((&x, &y), h) in x.iter().zip(y).zip(out) {
+        *h = xy2h_lowest(x, y, lowest_order);
+    }
+}
+
+/// Batch version of [`h2xy`](crate::h2xy) for a slice of hilbert indices.
+///
+/// # Panics
+/// If `h` and `out` differ in length.
+///
+/// # Examples
+///```
+/// let mut xy = [(0u32, 0u32); 2];
+/// fast_hilbert::h2xy_slice(&[3u64, 2], 1, &mut xy);
+/// assert_eq!(xy, [(1, 0), (1, 1)]);
+///```
+pub fn h2xy_slice<T: Unsigned>(h: &[T::Key], order: u8, out: &mut [(T, T)]) {
+    assert_eq!(
+        h.len(),
+        out.len(),
+        "input and output must have the same length"
+    );
+    let bits = h.iter().fold(T::Key::ZERO, |acc, &h| acc | h);
+    let lowest_order = h_lowest_order::<T>(bits, order);
+    for (&h, xy) in h.iter().zip(out) {
+        *xy = h2xy_lowest(h, lowest_order);
+    }
+}
+
+/// Batch version of [`h2xy`](crate::h2xy) which writes the coordinates into two separate slices.
+///
+/// # Panics
+/// If `h`, `x` and `y` differ in length.
+///
+/// # Examples
+///```
+/// let (mut x, mut y) = ([0u32; 2], [0u32; 2]);
+/// fast_hilbert::h2xy_slice_soa(&[3u64, 2], 1, &mut x, &mut y);
+/// assert_eq!((x, y), ([1, 1], [0, 1]));
+///```
+pub fn h2xy_slice_soa<T: Unsigned>(h: &[T::Key], order: u8, x: &mut [T], y: &mut [T]) {
+    assert_eq!(
+        h.len(),
+        x.len(),
+        "input and output must have the same length"
+    );
+    assert_eq!(
+        h.len(),
+        y.len(),
+        "input and output must have the same length"
+    );
+    let bits = h.iter().fold(T::Key::ZERO, |acc, &h| acc | h);
+    let lowest_order = h_lowest_order::<T>(bits, order);
+    for ((&h, x), y) in h.iter().zip(x).zip(y) {
+        (*x, *y) = h2xy_lowest(h, lowest_order);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{h2xy, max_order, xorshift, xy2h};
+
+    fn check_type<T: Unsigned + TryFrom<u64>>() {
+        let max_order = max_order::<T>();
+        let mut next = xorshift(0x2545_f491_4f6c_dd1d);
+        let values: Vec<u64> = (0..512).map(|_| next()).collect();
+        for order in [1, 2, 3, max_order - 1, max_order] {
+            for shift in [0, 60] {
+                let xy: Vec<(T, T)> = values
+                    .chunks(2)
+                    .map(|c| {
+                        let mask = if order >= 64 {
+                            u64::MAX
+                        } else {
+                            (1 << order) - 1
+                        };
+                        let x = T::try_from((c[0] >> shift) & mask).ok().unwrap();
+                        let y = T::try_from((c[1] >> shift) & mask).ok().unwrap();
+                        (x, y)
+                    })
+                    .collect();
+                let (x, y): (Vec<T>, Vec<T>) = xy.iter().copied().unzip();
+
+                let mut h = vec![T::Key::ZERO; xy.len()];
+                xy2h_slice(&xy, order, &mut h);
+                let mut h_soa = vec![T::Key::ZERO; xy.len()];
+                xy2h_slice_soa(&x, &y, order, &mut h_soa);
+                for ((&(x, y), &h), &h_soa) in xy.iter().zip(&h).zip(&h_soa) {
+                    assert_eq!(h, xy2h(x, y, order));
+                    assert_eq!(h_soa, h);
+                }
+
+                let mut pairs = vec![(T::ZERO, T::ZERO); h.len()];
+                h2xy_slice(&h, order, &mut pairs);
+                let mut xs = vec![T::ZERO; h.len()];
+                let mut ys = vec![T::ZERO; h.len()];
+                h2xy_slice_soa(&h, order, &mut xs, &mut ys);
+                for (i, &h) in h.iter().enumerate() {
+                    assert_eq!(pairs[i], h2xy::<T>(h, order));
+                    assert_eq!((xs[i], ys[i]), pairs[i]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn slice_matches_scalar() {
+        check_type::<u8>();
+        check_type::<u16>();
+        check_type::<u32>();
+        check_type::<u64>();
+    }
+
+    #[test]
+    fn empty_slices() {
+        xy2h_slice::<u32>(&[], 3, &mut []);
+        h2xy_slice::<u32>(&[], 3, &mut []);
+    }
+
+    #[test]
+    #[should_panic(expected = "same length")]
+    fn length_mismatch() {
+        xy2h_slice(&[(1u32, 2u32)], 3, &mut []);
+    }
+}
@@ -0,0 +1,155 @@
+use crate::{assert_order, h2xy, low_bits, xy2h, Unsigned, UnsignedBase};
+
+/// Convert a slippy map tile to its hilbert index.
+///
+/// The zoom level `z` is the order of the curve, so the tiles `0..2^z` of each axis are covered. Because the
+/// index of a tile is a prefix of the indices of all its descendants, a tile pyramid can be walked with
+/// [`parent`] and [`children`] without decoding the keys.
+///
+/// # Arguments
+/// * `z` - Zoom level
+/// * `x` - Tile column
+/// * `y` - Tile row
+///
+/// # Panics
+/// If `z` exceeds the number of bits of `T` or the tile is outside of the zoom level.
+///
+/// # Examples
+///```
+/// let h = fast_hilbert::tile_to_h(1, 1u32, 0);
+/// assert_eq!(h, 3u64);
+///```
+pub fn tile_to_h<T: Unsigned>(z: u8, x: T, y: T) -> T::Key {
+    assert_order::<T>(z);
+    let max: T = low_bits(z.into());
+    assert!(
+        x <= max && y <= max,
+        "tile ({x:?}, {y:?}) is outside of zoom level {z}"
+    );
+    xy2h(x, y, z)
+}
+
+/// Convert a hilbert index to its slippy map tile `(x, y)` at zoom level `z`.
+///
+/// # Arguments
+/// * `h` - Hilbert index of the tile
+/// * `z` - Zoom level
+///
+/// # Panics
+/// If `z` exceeds the number of bits of `T` or `h` exceeds the indices of the zoom level.
+///
+/// # Examples
+///```
+/// let (x, y) = fast_hilbert::h_to_tile::<u32>(3, 1);
+/// assert_eq!((x, y), (1, 0));
+///```
+pub fn h_to_tile<T: Unsigned>(h: T::Key, z: u8) -> (T, T) {
+    assert_order::<T>(z);
+    assert!(
+        h <= low_bits(u32::from(z) << 1),
+        "index {h:?} exceeds the indices of zoom level {z}"
+    );
+    h2xy(h, z)
+}
+
+/// Hilbert index of the parent tile at zoom level `z - 1`.
+///
+/// Returns `None` for the root tile at zoom level zero.
+///
+/// # Examples
+///```
+/// let h = fast_hilbert::tile_to_h(3, 5u32, 2);
+/// let parent = fast_hilbert::parent(h, 3);
+/// assert_eq!(parent, Some(fast_hilbert::tile_to_h(2, 2u32, 1)));
+///```
+pub fn parent<K: UnsignedBase>(h: K, z: u8) -> Option<K> {
+    (z > 0).then(|| h >> 2usize)
+}
+
+/// Hilbert indices of the four child tiles at zoom level `z + 1` in curve order.
+///
+/// Returns `None` if the indices of zoom level `z + 1` do not fit into the key type.
+///
+/// # Examples
+///```
+/// let h = fast_hilbert::tile_to_h(2, 2u32, 1);
+/// let children = fast_hilbert::children(h, 2).unwrap();
+/// assert!(children.contains(&fast_hilbert::tile_to_h(3, 5u32, 2)));
+///```
+pub fn children<K: UnsignedBase>(h: K, z: u8) -> Option<[K; 4]> {
+    let key_bits = size_of::<K>() << 3;
+    if (usize::from(z) + 1) << 1 > key_bits {
+        return None;
+    }
+    let first = h << 2usize;
+    Some([
+        first,
+        first | K::from(1),
+        first | K::from(2),
+        first | K::from(3),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tile_pyramid() {
+        for z in 1..=6u8 {
+            let n = 1u32 << z;
+            for x in 0..n {
+                for y in 0..n {
+                    let h = tile_to_h(z, x, y);
+                    assert_eq!(h_to_tile::<u32>(h, z), (x, y));
+                    assert_eq!(parent(h, z), Some(tile_to_h(z - 1, x >> 1, y >> 1)));
+
+                    let children = children(h, z).unwrap();
+                    let mut child_tiles = children.map(|child| h_to_tile::<u32>(child, z + 1));
+                    child_tiles.sort_unstable();
+                    let (x, y) = (x << 1, y << 1);
+                    assert_eq!(
+                        child_tiles,
+                        [(x, y), (x, y + 1), (x + 1, y), (x + 1, y + 1)]
+                    );
+                    for child in children {
+                        assert_eq!(parent(child, z + 1), Some(h));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn tile_limits() {
+        assert_eq!(parent(0u64, 0), None);
+        assert_eq!(children(0u64, 0), Some([0, 1, 2, 3]));
+        assert_eq!(children(0u64, 31).map(|c| c[3]), Some(3));
+        assert_eq!(children(0u64, 32), None);
+
+        let (x, y) = (u32::MAX, 123_456);
+        let h = tile_to_h(32, x, y);
+        assert_eq!(h_to_tile::<u32>(h, 32), (x, y));
+        let h = parent(h, 32).unwrap();
+        assert_eq!(h, tile_to_h(31, x >> 1, y >> 1));
+        assert!(children(h, 31).unwrap().contains(&tile_to_h(32, x, y)));
+    }
+
+    #[test]
+    #[should_panic(expected = "outside of zoom level")]
+    fn tile_outside_of_zoom() {
+        let _ = tile_to_h(2, 9u32, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds the indices of zoom level")]
+    fn index_beyond_zoom() {
+        let _ = h_to_tile::<u32>(16, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds the number of bits")]
+    fn zoom_beyond_type() {
+        let _ = tile_to_h(9, 0u8, 0);
+    }
+}